@@ -0,0 +1,143 @@
+use crate::data::cost::Cost;
+
+/// The kinds of cards that exist.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CardType {
+    Character,
+    Climax,
+    Event,
+}
+
+/// The colors a card can be.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Color {
+    Yellow,
+    Green,
+    Red,
+    Blue,
+}
+
+/// The trigger icons a card can have, checked during the trigger step.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TriggerIcon {
+    /// The attacking character gets +1 soul until end of turn.
+    Soul,
+    /// You may put the top card of your deck into your stock.
+    Pool,
+    /// Draw a card.
+    Draw,
+    /// You may return one of your opponent's characters to their hand.
+    Bounce,
+    /// You may return a climax from your waiting room to your hand.
+    Gate,
+    /// You may return a character from your waiting room to your hand.
+    Door,
+    /// You may draw a card.
+    Book,
+    /// You may put a character with a soul trigger icon from your
+    /// waiting room into your hand or stock.
+    Choice,
+    /// When the damage from this attack is cancelled, deal 1 damage.
+    Shot,
+    /// You may put a character from your waiting room with a level up to
+    /// one higher than yours onto your stage, rested.
+    Standby,
+}
+
+/// The effects an event can have when it resolves.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Effect {
+    /// Draw this many cards.
+    Draw(usize),
+    /// Put this many cards from the top of the deck into the stock.
+    Stock(usize),
+    /// Every character on the stage gets this much power until end of turn.
+    Power(i32),
+}
+
+/// The game events an automatic ability can trigger on.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Trigger {
+    /// This character is played onto the stage from hand.
+    Play,
+    /// This character stands during its controller's stand phase.
+    Stand,
+}
+
+/// The abilities a card can have.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Ability {
+    /// An encore ability with an alternative cost, usable in addition
+    /// to the (3) encore every character has.
+    Encore(Vec<Cost>),
+    /// A continuous ability that stops this card from standing
+    /// during its controller's stand phase.
+    DoesNotStand,
+    /// A continuous ability that stops this card from moving
+    /// to another position on the stage.
+    CannotMove,
+    /// A counter ability that gives the defending character `power`
+    /// until end of turn.  For characters this is an [ACT] Backup played
+    /// from hand by paying `cost`, and for events it is their effect.
+    Backup { power: i32, cost: Vec<Cost> },
+    /// An effect that resolves when this event is played.
+    Event(Effect),
+    /// An [AUTO] ability that triggers when `trigger` happens to this
+    /// card, resolving `effect` at the next check timing.
+    Auto { trigger: Trigger, effect: Effect },
+}
+
+/// The base statistics of a card, before anything in the game modifies them.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CardStats {
+    pub level: i32,
+    pub cost: i32,
+    pub power: i32,
+    pub soul: i32,
+    pub color: Color,
+}
+
+/// The static data of a single card.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CardData {
+    /// The code printed on the card, unique to each card in the game data.
+    pub code: String,
+    pub name: String,
+    /// The code of the title the card is from, e.g. `SMP` in `SMP/S01-001`.
+    pub title: String,
+    /// The code of the set the card was released in, e.g. `S01` in `SMP/S01-001`.
+    pub set: String,
+    pub card_type: CardType,
+    pub base_stats: CardStats,
+    pub traits: Vec<String>,
+    pub triggers: Vec<TriggerIcon>,
+    pub abilities: Vec<Ability>,
+    /// Whether the card has a counter icon, letting it be
+    /// played during the counter step.
+    pub counter: bool,
+}
+
+impl Default for CardData {
+    /// An unnamed level 0, cost 0, 3000 power, 1 soul yellow character with
+    /// no code, title, set, traits, triggers, abilities, or counter icon.
+    fn default() -> Self {
+        Self {
+            code: String::new(),
+            name: String::new(),
+            title: String::new(),
+            set: String::new(),
+            card_type: CardType::Character,
+            base_stats: CardStats {
+                level: 0,
+                cost: 0,
+                power: 3000,
+                soul: 1,
+                color: Color::Yellow,
+            },
+            traits: Vec::new(),
+            triggers: Vec::new(),
+            abilities: Vec::new(),
+            counter: false,
+        }
+    }
+}
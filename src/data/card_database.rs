@@ -0,0 +1,574 @@
+use crate::data::card_data::{Ability, CardData, CardType, Color, Effect, Trigger, TriggerIcon};
+use crate::data::cost::Cost;
+use std::fmt;
+
+/// A small card database bundled with the engine, used by tests.
+pub const SAMPLE_CARDS: &str = include_str!("../../data/sample_cards.txt");
+
+/// An error in a data file, such as a card database, found on `line`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// The line the error was found on, starting at 1.
+    pub line: usize,
+    /// The field the error was found in, if there was one.
+    pub field: Option<String>,
+    pub kind: ParseErrorKind,
+}
+
+/// The kinds of errors a data file can have.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// The line is not a header, a field, a comment, or blank.
+    MalformedLine,
+    /// A field was given before the first header.
+    FieldOutsideEntry,
+    /// The field isn't one the entry can have.
+    UnknownField,
+    /// The field was already given for this entry.
+    DuplicateField,
+    /// The entry is missing a required field.  Reported on
+    /// the entry's header line.
+    MissingField,
+    /// Another card already has this code.  Reported on the
+    /// card's header line.
+    DuplicateCode,
+    /// The field's value couldn't be read.
+    InvalidValue {
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}", self.line)?;
+        if let Some(field) = &self.field {
+            write!(f, ", field `{}`", field)?;
+        }
+        match &self.kind {
+            ParseErrorKind::MalformedLine => write!(f, ": expected a header or `field = value`"),
+            ParseErrorKind::FieldOutsideEntry => write!(f, ": field given before any header"),
+            ParseErrorKind::UnknownField => write!(f, ": unknown field"),
+            ParseErrorKind::DuplicateField => write!(f, ": field given more than once"),
+            ParseErrorKind::MissingField => write!(f, ": required field is missing"),
+            ParseErrorKind::DuplicateCode => write!(f, ": another card has the same code"),
+            ParseErrorKind::InvalidValue { value, expected } => {
+                write!(f, ": expected {}, found `{}`", expected, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An entry of a data file that is still being parsed.  Each entry
+/// starts with its header line, followed by its fields.
+pub(crate) trait Entry: Sized {
+    /// What the entry becomes once it's parsed.
+    type Output;
+    /// The header line that starts each entry, e.g. `[card]`.
+    const HEADER: &'static str;
+
+    /// Starts an entry whose header is on `line`.
+    fn new(line: usize) -> Self;
+
+    /// Sets the `field` of the entry from its `value`.
+    fn set(&mut self, field: &str, value: &str) -> Result<(), ParseErrorKind>;
+
+    /// Finishes parsing the entry, given every entry before it.
+    fn finish(self, previous: &[Self::Output]) -> Result<Self::Output, ParseError>;
+}
+
+/// Parses a data file made of entries, returning them in the order they're listed.
+///
+/// Blank lines and lines starting with `#` are ignored.  Each entry starts
+/// with its header line, followed by its fields, one `field = value` per line.
+///
+/// Returns the first error in the file, if there is one.
+pub(crate) fn parse_entries<E: Entry>(text: &str) -> Result<Vec<E::Output>, ParseError> {
+    let mut entries = Vec::new();
+    let mut current: Option<E> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line == E::HEADER {
+            if let Some(entry) = current.take() {
+                entries.push(entry.finish(&entries)?);
+            }
+            current = Some(E::new(line_number));
+            continue;
+        }
+
+        let (field, value) = line.split_once('=').ok_or(ParseError {
+            line: line_number,
+            field: None,
+            kind: ParseErrorKind::MalformedLine,
+        })?;
+        let (field, value) = (field.trim(), value.trim());
+
+        let entry = current.as_mut().ok_or_else(|| ParseError {
+            line: line_number,
+            field: Some(field.to_string()),
+            kind: ParseErrorKind::FieldOutsideEntry,
+        })?;
+
+        entry.set(field, value).map_err(|kind| ParseError {
+            line: line_number,
+            field: Some(field.to_string()),
+            kind,
+        })?;
+    }
+
+    if let Some(entry) = current {
+        entries.push(entry.finish(&entries)?);
+    }
+
+    Ok(entries)
+}
+
+/// Returns an error for the `value` of a field, which should have been `expected`.
+pub(crate) fn invalid(value: &str, expected: &'static str) -> ParseErrorKind {
+    ParseErrorKind::InvalidValue {
+        value: value.to_string(),
+        expected,
+    }
+}
+
+/// Splits a comma separated list, skipping empty items.
+pub(crate) fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// Returns the first of the `required` fields that isn't in `given`.
+pub(crate) fn missing_field<'a>(required: &[&'a str], given: &[String]) -> Option<&'a str> {
+    required
+        .iter()
+        .copied()
+        .find(|required| !given.iter().any(|given| given == required))
+}
+
+/// The fields every card has to give.
+const REQUIRED_FIELDS: [&str; 3] = ["code", "name", "type"];
+
+/// Parses a card database, returning its cards in the order they're
+/// listed.  The index of each card is its CardDataId.
+///
+/// A database is made of lines.  Blank lines and lines starting with `#`
+/// are ignored.  Each card starts with a `[card]` header line, followed by
+/// its fields, one `field = value` per line:
+///
+/// - `code`: required, the card's code, which no other card can share.
+/// - `name`: required, the card's name.
+/// - `type`: required, one of `character`, `event`, or `climax`.
+/// - `level`, `cost`, `power`, `soul`: numbers, defaulting to 0, 0, 3000 and 1.
+/// - `color`: one of `yellow`, `green`, `red`, or `blue`, defaulting to yellow.
+/// - `title` and `set`: the codes of the title and set the card is from.
+/// - `traits`: a comma separated list of traits.
+/// - `triggers`: a comma separated list of trigger icons, e.g. `soul, soul`.
+/// - `counter`: `true` or `false`, whether the card has a counter icon.
+/// - `ability`: one ability, and can be given once for each ability.
+///
+/// Abilities are one of:
+///
+/// - `does not stand` or `cannot move`.
+/// - `encore: <costs>`, an alternative encore cost.
+/// - `backup <power>: <costs>`, with the costs being optional.
+/// - `draw <n>`, `stock <n>`, or `power <n>`, an event's effect.
+/// - `when played: <effect>` or `when stood: <effect>`, an automatic
+///   ability with one of the effects above.
+///
+/// Costs are a comma separated list of `stock <n>`, `discard <n>`,
+/// `discard character <n>`, `clock from deck`, `rest this`, or
+/// `this to memory`.
+///
+/// Returns the first error in the database, if there is one.
+pub fn parse_cards(text: &str) -> Result<Vec<CardData>, ParseError> {
+    parse_entries::<CardEntry>(text)
+}
+
+/// A card that is still being parsed.
+struct CardEntry {
+    /// The line of the card's header.
+    line: usize,
+    data: CardData,
+    /// The fields given so far.
+    given: Vec<String>,
+}
+
+impl Entry for CardEntry {
+    type Output = CardData;
+    const HEADER: &'static str = "[card]";
+
+    fn new(line: usize) -> Self {
+        Self {
+            line,
+            data: CardData::default(),
+            given: Vec::new(),
+        }
+    }
+
+    /// Sets the `field` of the card from its `value`.
+    fn set(&mut self, field: &str, value: &str) -> Result<(), ParseErrorKind> {
+        if field != "ability" && self.given.iter().any(|given| given == field) {
+            return Err(ParseErrorKind::DuplicateField);
+        }
+
+        let data = &mut self.data;
+        match field {
+            "code" if value.is_empty() => return Err(invalid(value, "a code")),
+            "code" => data.code = value.to_string(),
+            "name" if value.is_empty() => return Err(invalid(value, "a name")),
+            "name" => data.name = value.to_string(),
+            "title" => data.title = value.to_string(),
+            "set" => data.set = value.to_string(),
+            "type" => data.card_type = parse_card_type(value)?,
+            "level" => data.base_stats.level = parse_number(value)?,
+            "cost" => data.base_stats.cost = parse_number(value)?,
+            "power" => data.base_stats.power = parse_number(value)?,
+            "soul" => data.base_stats.soul = parse_number(value)?,
+            "color" => data.base_stats.color = parse_color(value)?,
+            "traits" => data.traits = split_list(value).map(str::to_string).collect(),
+            "triggers" => {
+                data.triggers = split_list(value)
+                    .map(parse_trigger)
+                    .collect::<Result<_, _>>()?
+            }
+            "counter" => {
+                data.counter = match value {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(invalid(value, "`true` or `false`")),
+                }
+            }
+            "ability" => data.abilities.push(parse_ability(value)?),
+            _ => return Err(ParseErrorKind::UnknownField),
+        }
+
+        self.given.push(field.to_string());
+        Ok(())
+    }
+
+    /// Finishes parsing the card, checking every required field was given,
+    /// and that none of the `previous` cards have the same code.
+    fn finish(self, previous: &[CardData]) -> Result<CardData, ParseError> {
+        if let Some(missing) = missing_field(&REQUIRED_FIELDS, &self.given) {
+            return Err(ParseError {
+                line: self.line,
+                field: Some(missing.to_string()),
+                kind: ParseErrorKind::MissingField,
+            });
+        }
+
+        if previous.iter().any(|card| card.code == self.data.code) {
+            return Err(ParseError {
+                line: self.line,
+                field: Some("code".to_string()),
+                kind: ParseErrorKind::DuplicateCode,
+            });
+        }
+
+        Ok(self.data)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, ParseErrorKind> {
+    value.parse().map_err(|_| invalid(value, "a number"))
+}
+
+fn parse_card_type(value: &str) -> Result<CardType, ParseErrorKind> {
+    match value {
+        "character" => Ok(CardType::Character),
+        "event" => Ok(CardType::Event),
+        "climax" => Ok(CardType::Climax),
+        _ => Err(invalid(value, "`character`, `event`, or `climax`")),
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color, ParseErrorKind> {
+    match value {
+        "yellow" => Ok(Color::Yellow),
+        "green" => Ok(Color::Green),
+        "red" => Ok(Color::Red),
+        "blue" => Ok(Color::Blue),
+        _ => Err(invalid(value, "`yellow`, `green`, `red`, or `blue`")),
+    }
+}
+
+fn parse_trigger(value: &str) -> Result<TriggerIcon, ParseErrorKind> {
+    match value {
+        "soul" => Ok(TriggerIcon::Soul),
+        "pool" => Ok(TriggerIcon::Pool),
+        "draw" => Ok(TriggerIcon::Draw),
+        "bounce" => Ok(TriggerIcon::Bounce),
+        "gate" => Ok(TriggerIcon::Gate),
+        "door" => Ok(TriggerIcon::Door),
+        "book" => Ok(TriggerIcon::Book),
+        "choice" => Ok(TriggerIcon::Choice),
+        "shot" => Ok(TriggerIcon::Shot),
+        "standby" => Ok(TriggerIcon::Standby),
+        _ => Err(invalid(value, "a trigger icon")),
+    }
+}
+
+fn parse_ability(value: &str) -> Result<Ability, ParseErrorKind> {
+    if let Some((trigger, effect)) = value.split_once(':') {
+        if let Some(trigger) = trigger.trim().strip_prefix("when ") {
+            return Ok(Ability::Auto {
+                trigger: parse_auto_trigger(trigger)?,
+                effect: parse_effect(effect.trim())?,
+            });
+        }
+    }
+
+    let (head, cost) = match value.split_once(':') {
+        Some((head, cost)) => (head.trim(), Some(parse_costs(cost)?)),
+        None => (value, None),
+    };
+    let (name, amount) = match head.rsplit_once(' ') {
+        Some((name, amount)) => (name, Some(amount)),
+        None => (head, None),
+    };
+
+    match (name, amount, cost) {
+        ("does not", Some("stand"), None) => Ok(Ability::DoesNotStand),
+        ("cannot", Some("move"), None) => Ok(Ability::CannotMove),
+        ("encore", None, Some(cost)) => Ok(Ability::Encore(cost)),
+        ("backup", Some(power), cost) => Ok(Ability::Backup {
+            power: parse_number(power)?,
+            cost: cost.unwrap_or_default(),
+        }),
+        ("draw" | "stock" | "power", Some(_), None) => parse_effect(head).map(Ability::Event),
+        _ => Err(invalid(value, "an ability")),
+    }
+}
+
+fn parse_auto_trigger(value: &str) -> Result<Trigger, ParseErrorKind> {
+    match value {
+        "played" => Ok(Trigger::Play),
+        "stood" => Ok(Trigger::Stand),
+        _ => Err(invalid(value, "a trigger")),
+    }
+}
+
+fn parse_effect(value: &str) -> Result<Effect, ParseErrorKind> {
+    match value.rsplit_once(' ') {
+        Some(("draw", amount)) => Ok(Effect::Draw(parse_number(amount)?)),
+        Some(("stock", amount)) => Ok(Effect::Stock(parse_number(amount)?)),
+        Some(("power", amount)) => Ok(Effect::Power(parse_number(amount)?)),
+        _ => Err(invalid(value, "an effect")),
+    }
+}
+
+fn parse_costs(value: &str) -> Result<Vec<Cost>, ParseErrorKind> {
+    split_list(value).map(parse_cost).collect()
+}
+
+fn parse_cost(value: &str) -> Result<Cost, ParseErrorKind> {
+    match value {
+        "clock from deck" => return Ok(Cost::ClockFromDeck),
+        "rest this" => return Ok(Cost::RestThis),
+        "this to memory" => return Ok(Cost::ThisToMemory),
+        _ => {}
+    }
+
+    let (name, amount) = value
+        .rsplit_once(' ')
+        .ok_or_else(|| invalid(value, "a cost"))?;
+    let amount = parse_number(amount)?;
+    match name {
+        "stock" => Ok(Cost::Stock(amount)),
+        "discard" => Ok(Cost::DiscardFromHand(amount)),
+        "discard character" => Ok(Cost::DiscardCharacterFromHand(amount)),
+        _ => Err(invalid(value, "a cost")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> ParseError {
+        parse_cards(text).unwrap_err()
+    }
+
+    #[test]
+    fn sample_cards() {
+        let cards = parse_cards(SAMPLE_CARDS).unwrap();
+
+        assert_eq!(cards.len(), 15);
+        assert!(cards.iter().all(|card| !card.name.is_empty()));
+    }
+
+    #[test]
+    fn parse_card() {
+        let cards = parse_cards(
+            "# a comment\n\
+             [card]\n\
+             code = TST/01-001\n\
+             name = Backup Girl\n\
+             title = TST\n\
+             set = 01\n\
+             type = character\n\
+             level = 1\n\
+             power = 2500\n\
+             color = red\n\
+             traits = Music, Idol\n\
+             triggers = soul\n\
+             counter = true\n\
+             ability = backup 2000: stock 1, discard 1\n\
+             ability = encore: discard character 1\n\
+             \n\
+             [card]\n\
+             code = TST/01-002\n\
+             name = Draw Event\n\
+             type = event\n\
+             ability = draw 2\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            cards[0],
+            CardData {
+                code: "TST/01-001".to_string(),
+                name: "Backup Girl".to_string(),
+                title: "TST".to_string(),
+                set: "01".to_string(),
+                card_type: CardType::Character,
+                base_stats: crate::data::card_data::CardStats {
+                    level: 1,
+                    cost: 0,
+                    power: 2500,
+                    soul: 1,
+                    color: Color::Red,
+                },
+                traits: vec!["Music".to_string(), "Idol".to_string()],
+                triggers: vec![TriggerIcon::Soul],
+                abilities: vec![
+                    Ability::Backup {
+                        power: 2000,
+                        cost: vec![Cost::Stock(1), Cost::DiscardFromHand(1)],
+                    },
+                    Ability::Encore(vec![Cost::DiscardCharacterFromHand(1)]),
+                ],
+                counter: true,
+            }
+        );
+        assert_eq!(cards[1].card_type, CardType::Event);
+        assert_eq!(cards[1].abilities, vec![Ability::Event(Effect::Draw(2))]);
+    }
+
+    #[test]
+    fn parse_abilities() {
+        let cases = [
+            ("does not stand", Ability::DoesNotStand),
+            ("cannot move", Ability::CannotMove),
+            (
+                "encore: clock from deck, rest this",
+                Ability::Encore(vec![Cost::ClockFromDeck, Cost::RestThis]),
+            ),
+            (
+                "backup 1000",
+                Ability::Backup {
+                    power: 1000,
+                    cost: vec![],
+                },
+            ),
+            (
+                "backup 3000: this to memory",
+                Ability::Backup {
+                    power: 3000,
+                    cost: vec![Cost::ThisToMemory],
+                },
+            ),
+            ("stock 1", Ability::Event(Effect::Stock(1))),
+            ("power -500", Ability::Event(Effect::Power(-500))),
+            (
+                "when played: draw 1",
+                Ability::Auto {
+                    trigger: Trigger::Play,
+                    effect: Effect::Draw(1),
+                },
+            ),
+        ];
+
+        for (text, ability) in cases.iter() {
+            assert_eq!(parse_ability(text).as_ref(), Ok(ability));
+        }
+        assert!(parse_ability("fly 3").is_err());
+        assert!(parse_ability("encore").is_err());
+        assert_eq!(
+            parse_ability("when stood: power 500"),
+            Ok(Ability::Auto {
+                trigger: Trigger::Stand,
+                effect: Effect::Power(500),
+            })
+        );
+        assert!(parse_ability("when attacking: draw 1").is_err());
+        assert!(parse_ability("when played: fly 3").is_err());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            error("[card]\ncode = A\nname = A\ntype = character\npower = lots\n"),
+            ParseError {
+                line: 5,
+                field: Some("power".to_string()),
+                kind: ParseErrorKind::InvalidValue {
+                    value: "lots".to_string(),
+                    expected: "a number",
+                },
+            }
+        );
+        assert_eq!(error("name = A\n").kind, ParseErrorKind::FieldOutsideEntry);
+        assert_eq!(
+            error("[card]\ncode = A\nname = A\ntype = event\n[card]\ncode = A\nname = B\ntype = event\n"),
+            ParseError {
+                line: 5,
+                field: Some("code".to_string()),
+                kind: ParseErrorKind::DuplicateCode,
+            }
+        );
+        assert_eq!(
+            error("[card]\nname A\n").kind,
+            ParseErrorKind::MalformedLine
+        );
+        assert_eq!(
+            error("[card]\nnaem = A\n").kind,
+            ParseErrorKind::UnknownField
+        );
+        assert_eq!(
+            error("[card]\nname = A\nname = B\n").kind,
+            ParseErrorKind::DuplicateField
+        );
+        assert_eq!(
+            error("[card]\ncode = A\ntype = climax\n\n[card]\nname = B\n"),
+            ParseError {
+                line: 1,
+                field: Some("name".to_string()),
+                kind: ParseErrorKind::MissingField,
+            }
+        );
+    }
+
+    #[test]
+    fn error_message() {
+        assert_eq!(
+            error("\n[card]\ncode = A\nname = A\ncolor = purple\n").to_string(),
+            "line 5, field `color`: expected `yellow`, `green`, `red`, or `blue`, found `purple`"
+        );
+        assert_eq!(
+            error("[card]\nname = A\n").to_string(),
+            "line 1, field `code`: required field is missing"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+/// A single part of a cost that a player can pay.  Costs
+/// made up of several parts are paid in order.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Cost {
+    /// Put the top N cards of your stock into your waiting room.
+    Stock(usize),
+    /// Put N cards from your hand into your waiting room.
+    DiscardFromHand(usize),
+    /// Put N characters from your hand into your waiting room.
+    DiscardCharacterFromHand(usize),
+    /// Put the top card of your deck into your clock.
+    ClockFromDeck,
+    /// Rest this card, which must be a standing character on your stage.
+    RestThis,
+    /// Put this card into your memory.
+    ThisToMemory,
+}
@@ -0,0 +1,319 @@
+use crate::data::card_data::CardType;
+use crate::data::game_data::GameData;
+use crate::data::CardDataId;
+use std::fmt;
+
+/// The number of cards a deck must have.
+pub const DECK_SIZE: usize = 50;
+/// The most copies of cards with the same name a deck can have.
+pub const MAX_COPIES: usize = 4;
+/// The most climaxes a deck can have.
+pub const MAX_CLIMAXES: usize = 8;
+
+/// A sample deck list bundled with the engine, made from the
+/// cards in [`SAMPLE_CARDS`](crate::data::card_database::SAMPLE_CARDS).
+pub const SAMPLE_DECK: &str = include_str!("../../data/sample_deck.txt");
+
+/// A problem with a deck list.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DeckError {
+    /// The line is not `code quantity`.
+    MalformedLine { line: usize },
+    /// No card in the game data has the code.
+    UnknownCode { line: usize, code: String },
+    /// The quantity isn't a positive number.
+    InvalidQuantity { line: usize, value: String },
+    /// The deck doesn't have exactly [`DECK_SIZE`] cards.
+    WrongSize { count: usize },
+    /// The deck has more than [`MAX_COPIES`] cards with the same name.
+    TooManyCopies { name: String, count: usize },
+    /// The deck has more than [`MAX_CLIMAXES`] climaxes.
+    TooManyClimaxes { count: usize },
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeckError::MalformedLine { line } => {
+                write!(f, "line {}: expected `code quantity`", line)
+            }
+            DeckError::UnknownCode { line, code } => {
+                write!(f, "line {}: no card has the code `{}`", line, code)
+            }
+            DeckError::InvalidQuantity { line, value } => write!(
+                f,
+                "line {}: expected a positive quantity, found `{}`",
+                line, value
+            ),
+            DeckError::WrongSize { count } => write!(
+                f,
+                "deck has {} cards, but must have exactly {}",
+                count, DECK_SIZE
+            ),
+            DeckError::TooManyCopies { name, count } => write!(
+                f,
+                "deck has {} copies of `{}`, but can have at most {}",
+                count, name, MAX_COPIES
+            ),
+            DeckError::TooManyClimaxes { count } => write!(
+                f,
+                "deck has {} climaxes, but can have at most {}",
+                count, MAX_CLIMAXES
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
+/// The cards in a deck, and how many copies of each.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DeckList {
+    pub cards: Vec<(CardDataId, usize)>,
+}
+
+impl DeckList {
+    /// Parses a deck list, resolving its cards against the `data`.
+    /// The deck isn't checked against the deck construction rules,
+    /// use [`DeckList::validate`] for that.
+    ///
+    /// A deck list is made of lines.  Blank lines and lines starting with
+    /// `#` are ignored.  Every other line is a card's code followed by
+    /// how many copies of it are in the deck, e.g. `SMP/S01-001 4`.
+    ///
+    /// Returns every line that couldn't be parsed, if there are any.
+    pub fn parse(text: &str, data: &GameData) -> Result<Self, Vec<DeckError>> {
+        let mut cards: Vec<(CardDataId, usize)> = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match parse_line(line, line_number, data) {
+                Ok((card, quantity)) => match cards.iter_mut().find(|(id, _)| *id == card) {
+                    Some((_, total)) => *total += quantity,
+                    None => cards.push((card, quantity)),
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Self { cards })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the number of cards in the deck.
+    pub fn card_count(&self) -> usize {
+        self.cards.iter().map(|(_, quantity)| quantity).sum()
+    }
+
+    /// Checks the deck against the deck construction rules, returning
+    /// every rule it breaks.
+    ///
+    /// 1) The deck has exactly [`DECK_SIZE`] cards.
+    ///
+    /// 2) The deck has at most [`MAX_COPIES`] cards with the same name,
+    ///    even if their codes are different.
+    ///
+    /// 3) The deck has at most [`MAX_CLIMAXES`] climaxes.
+    pub fn validate(&self, data: &GameData) -> Vec<DeckError> {
+        let mut errors = Vec::new();
+
+        let count = self.card_count();
+        if count != DECK_SIZE {
+            errors.push(DeckError::WrongSize { count });
+        }
+
+        let mut names: Vec<(&str, usize)> = Vec::new();
+        for (card, quantity) in self.cards.iter() {
+            let name = data.card(*card).name.as_str();
+            match names.iter_mut().find(|(other, _)| *other == name) {
+                Some((_, total)) => *total += quantity,
+                None => names.push((name, *quantity)),
+            }
+        }
+        errors.extend(
+            names
+                .into_iter()
+                .filter(|(_, count)| *count > MAX_COPIES)
+                .map(|(name, count)| DeckError::TooManyCopies {
+                    name: name.to_string(),
+                    count,
+                }),
+        );
+
+        let count = self
+            .cards
+            .iter()
+            .filter(|(card, _)| data.card(*card).card_type == CardType::Climax)
+            .map(|(_, quantity)| quantity)
+            .sum();
+        if count > MAX_CLIMAXES {
+            errors.push(DeckError::TooManyClimaxes { count });
+        }
+
+        errors
+    }
+
+    /// Returns every card in the deck, with each copy listed separately.
+    pub fn copies(&self) -> impl Iterator<Item = CardDataId> + '_ {
+        self.cards
+            .iter()
+            .flat_map(|(card, quantity)| std::iter::repeat_n(*card, *quantity))
+    }
+}
+
+/// Parses a single `code quantity` line of a deck list.
+fn parse_line(
+    line: &str,
+    line_number: usize,
+    data: &GameData,
+) -> Result<(CardDataId, usize), DeckError> {
+    let (code, quantity) = line
+        .rsplit_once(char::is_whitespace)
+        .ok_or(DeckError::MalformedLine { line: line_number })?;
+    let (code, quantity) = (code.trim(), quantity.trim());
+
+    let card = data.find_code(code).ok_or_else(|| DeckError::UnknownCode {
+        line: line_number,
+        code: code.to_string(),
+    })?;
+    let quantity = quantity
+        .parse()
+        .ok()
+        .filter(|quantity| *quantity > 0)
+        .ok_or_else(|| DeckError::InvalidQuantity {
+            line: line_number,
+            value: quantity.to_string(),
+        })?;
+
+    Ok((card, quantity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::card_database::SAMPLE_CARDS;
+
+    fn sample_data() -> GameData {
+        GameData::parse(SAMPLE_CARDS).unwrap()
+    }
+
+    #[test]
+    fn sample_deck() {
+        let data = sample_data();
+        let deck = DeckList::parse(SAMPLE_DECK, &data).unwrap();
+
+        assert!(deck.validate(&data).is_empty());
+        assert_eq!(deck.card_count(), DECK_SIZE);
+        assert_eq!(deck.copies().count(), DECK_SIZE);
+        assert_eq!(deck.cards[0], (0.into(), 4));
+    }
+
+    #[test]
+    fn parse_merges_lines() {
+        let mut data = sample_data();
+        data.cards[1].name = data.cards[0].name.clone();
+        let text = "SMP/S01-001 2\nSMP/S01-001 1\nSMP/S01-002 1\n";
+
+        let deck = DeckList::parse(text, &data).unwrap();
+
+        assert_eq!(deck.cards, vec![(0.into(), 3), (1.into(), 1)]);
+        assert_eq!(
+            deck.validate(&data),
+            vec![DeckError::WrongSize { count: 4 }]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let data = sample_data();
+        let text = "# comment\n\
+                    SMP/S01-001\n\
+                    SMP/S01-999 4\n\
+                    SMP/S01-002 many\n\
+                    SMP/S01-003 0\n";
+
+        assert_eq!(
+            DeckList::parse(text, &data).unwrap_err(),
+            vec![
+                DeckError::MalformedLine { line: 2 },
+                DeckError::UnknownCode {
+                    line: 3,
+                    code: "SMP/S01-999".to_string()
+                },
+                DeckError::InvalidQuantity {
+                    line: 4,
+                    value: "many".to_string()
+                },
+                DeckError::InvalidQuantity {
+                    line: 5,
+                    value: "0".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_every_violation() {
+        let mut data = sample_data();
+        data.cards[1].name = data.cards[0].name.clone();
+        let deck = DeckList {
+            cards: vec![
+                (0.into(), 3),
+                (1.into(), 2),
+                (2.into(), 5),
+                (12.into(), 5),
+                (13.into(), 4),
+            ],
+        };
+
+        assert_eq!(
+            deck.validate(&data),
+            vec![
+                DeckError::WrongSize { count: 19 },
+                DeckError::TooManyCopies {
+                    name: data.cards[0].name.clone(),
+                    count: 5
+                },
+                DeckError::TooManyCopies {
+                    name: data.cards[2].name.clone(),
+                    count: 5
+                },
+                DeckError::TooManyCopies {
+                    name: data.cards[12].name.clone(),
+                    count: 5
+                },
+                DeckError::TooManyClimaxes { count: 9 },
+            ]
+        );
+    }
+
+    #[test]
+    fn error_message() {
+        assert_eq!(
+            DeckError::TooManyCopies {
+                name: "Main Vocalist".to_string(),
+                count: 6
+            }
+            .to_string(),
+            "deck has 6 copies of `Main Vocalist`, but can have at most 4"
+        );
+        assert_eq!(
+            DeckError::UnknownCode {
+                line: 3,
+                code: "ABC".to_string()
+            }
+            .to_string(),
+            "line 3: no card has the code `ABC`"
+        );
+    }
+}
@@ -0,0 +1,428 @@
+use crate::data::card_data::CardData;
+use crate::data::card_database::{
+    invalid, missing_field, parse_entries, split_list, Entry, ParseError, ParseErrorKind,
+};
+use crate::data::deck_list::{DeckError, DeckList};
+use crate::data::game_data::{GameData, LoadError};
+use std::fmt;
+use std::path::Path;
+
+/// A sample list of formats bundled with the engine, for the
+/// cards in [`SAMPLE_CARDS`](crate::data::card_database::SAMPLE_CARDS).
+pub const SAMPLE_FORMATS: &str = include_str!("../../data/sample_formats.txt");
+
+/// The kinds of formats a tournament can use.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FormatKind {
+    /// Decks can use cards from any of a group of titles.
+    NeoStandard,
+    /// Decks can only use cards from a single one of the format's titles.
+    TitleSpecific,
+}
+
+/// The rules a deck has to follow to be played in a format,
+/// on top of the deck construction rules.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Format {
+    pub name: String,
+    pub kind: FormatKind,
+    /// The titles decks can use cards from.
+    pub titles: Vec<String>,
+    /// Sets decks can use cards from, even if their title isn't in the format.
+    pub sets: Vec<String>,
+    /// The codes of cards decks can't use.
+    pub banned: Vec<String>,
+    /// Groups of card codes, where decks can only use one card from each group.
+    pub restricted: Vec<Vec<String>>,
+}
+
+/// A reason a deck can't be played in a format.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FormatViolation {
+    /// The deck breaks a deck construction rule.
+    Construction(DeckError),
+    /// The card's title and set aren't in the format.
+    NotInFormat { code: String },
+    /// The card is banned in the format.
+    Banned { code: String },
+    /// The deck uses more than one of the cards from a restricted group.
+    Restricted { codes: Vec<String> },
+    /// The deck uses cards from more than one title in a title-specific format.
+    MixedTitles { titles: Vec<String> },
+}
+
+impl fmt::Display for FormatViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatViolation::Construction(error) => write!(f, "{}", error),
+            FormatViolation::NotInFormat { code } => {
+                write!(f, "`{}` is not from a title or set in the format", code)
+            }
+            FormatViolation::Banned { code } => write!(f, "`{}` is banned", code),
+            FormatViolation::Restricted { codes } => write!(
+                f,
+                "only one of `{}` can be used together",
+                codes.join("`, `")
+            ),
+            FormatViolation::MixedTitles { titles } => write!(
+                f,
+                "cards from `{}` can't be used together",
+                titles.join("`, `")
+            ),
+        }
+    }
+}
+
+/// Whether a deck can be played in a format, and why not.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FormatReport {
+    /// The name of the format.
+    pub format: String,
+    pub violations: Vec<FormatViolation>,
+}
+
+impl FormatReport {
+    /// Checks to see if the deck can be played in the format.
+    pub fn is_legal(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Format {
+    /// Checks to see if the `card`'s title or set is in this format.
+    pub fn allows(&self, card: &CardData) -> bool {
+        self.titles.contains(&card.title) || self.sets.contains(&card.set)
+    }
+
+    /// Checks the `deck` against this format, reporting every rule it breaks.
+    ///
+    /// 1) The deck follows the deck construction rules.
+    ///
+    /// 2) Every card in the deck is from a title or set in this format,
+    ///    and isn't banned.
+    ///
+    /// 3) The deck uses at most one card from each restricted group.
+    ///
+    /// 4) In a title-specific format, the deck uses cards from at most
+    ///    one of the format's titles.
+    pub fn check(&self, deck: &DeckList, data: &GameData) -> FormatReport {
+        let mut violations: Vec<_> = deck
+            .validate(data)
+            .into_iter()
+            .map(FormatViolation::Construction)
+            .collect();
+
+        for (card, _) in deck.cards.iter() {
+            let card = data.card(*card);
+            if !self.allows(card) {
+                violations.push(FormatViolation::NotInFormat {
+                    code: card.code.clone(),
+                });
+            }
+            if self.banned.contains(&card.code) {
+                violations.push(FormatViolation::Banned {
+                    code: card.code.clone(),
+                });
+            }
+        }
+
+        for group in self.restricted.iter() {
+            let codes: Vec<_> = group
+                .iter()
+                .filter(|code| {
+                    deck.cards
+                        .iter()
+                        .any(|(card, _)| data.card(*card).code == **code)
+                })
+                .cloned()
+                .collect();
+            if codes.len() > 1 {
+                violations.push(FormatViolation::Restricted { codes });
+            }
+        }
+
+        if self.kind == FormatKind::TitleSpecific {
+            let titles: Vec<_> = self
+                .titles
+                .iter()
+                .filter(|title| {
+                    deck.cards
+                        .iter()
+                        .any(|(card, _)| data.card(*card).title == **title)
+                })
+                .cloned()
+                .collect();
+            if titles.len() > 1 {
+                violations.push(FormatViolation::MixedTitles { titles });
+            }
+        }
+
+        FormatReport {
+            format: self.name.clone(),
+            violations,
+        }
+    }
+
+    /// Parses a list of formats.
+    ///
+    /// Formats are written like a card database.  Each format starts with
+    /// a `[format]` header line, followed by its fields:
+    ///
+    /// - `name`: required, the format's name.
+    /// - `kind`: required, either `neo-standard` or `title-specific`.
+    /// - `titles`: required, a comma separated list of title codes.
+    ///   Decks in a title-specific format pick one of them.
+    /// - `sets`: a comma separated list of set codes.
+    /// - `banned`: a comma separated list of banned card codes.
+    /// - `restricted`: a comma separated group of two or more card codes, where
+    ///   decks can only use one of them.  Can be given once for each group.
+    ///
+    /// Returns the first error in the list, if there is one.
+    pub fn parse_all(text: &str) -> Result<Vec<Self>, ParseError> {
+        parse_entries::<FormatEntry>(text)
+    }
+
+    /// Loads the list of formats in the file at `path`.
+    pub fn load_all<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, LoadError> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Self::parse_all(&text)?)
+    }
+}
+
+/// Checks the `deck` against each of the `formats`, with one
+/// report for each format, in the same order.
+pub fn check_formats(deck: &DeckList, data: &GameData, formats: &[Format]) -> Vec<FormatReport> {
+    formats
+        .iter()
+        .map(|format| format.check(deck, data))
+        .collect()
+}
+
+/// The fields every format has to give.
+const REQUIRED_FIELDS: [&str; 3] = ["name", "kind", "titles"];
+
+/// A format that is still being parsed.
+struct FormatEntry {
+    /// The line of the format's header.
+    line: usize,
+    format: Format,
+    /// The fields given so far.
+    given: Vec<String>,
+}
+
+impl Entry for FormatEntry {
+    type Output = Format;
+    const HEADER: &'static str = "[format]";
+
+    fn new(line: usize) -> Self {
+        Self {
+            line,
+            format: Format {
+                name: String::new(),
+                kind: FormatKind::NeoStandard,
+                titles: Vec::new(),
+                sets: Vec::new(),
+                banned: Vec::new(),
+                restricted: Vec::new(),
+            },
+            given: Vec::new(),
+        }
+    }
+
+    fn set(&mut self, field: &str, value: &str) -> Result<(), ParseErrorKind> {
+        if field != "restricted" && self.given.iter().any(|given| given == field) {
+            return Err(ParseErrorKind::DuplicateField);
+        }
+
+        let list = || split_list(value).map(str::to_string).collect::<Vec<_>>();
+        let format = &mut self.format;
+        match field {
+            "name" if value.is_empty() => return Err(invalid(value, "a name")),
+            "name" => format.name = value.to_string(),
+            "kind" => {
+                format.kind = match value {
+                    "neo-standard" => FormatKind::NeoStandard,
+                    "title-specific" => FormatKind::TitleSpecific,
+                    _ => return Err(invalid(value, "`neo-standard` or `title-specific`")),
+                }
+            }
+            "titles" if list().is_empty() => return Err(invalid(value, "a list of titles")),
+            "titles" => format.titles = list(),
+            "sets" => format.sets = list(),
+            "banned" => format.banned = list(),
+            "restricted" if list().len() < 2 => {
+                return Err(invalid(value, "a list of two or more codes"))
+            }
+            "restricted" => format.restricted.push(list()),
+            _ => return Err(ParseErrorKind::UnknownField),
+        }
+
+        self.given.push(field.to_string());
+        Ok(())
+    }
+
+    /// Finishes parsing the format, checking every required field was given.
+    fn finish(self, _: &[Format]) -> Result<Format, ParseError> {
+        if let Some(missing) = missing_field(&REQUIRED_FIELDS, &self.given) {
+            return Err(ParseError {
+                line: self.line,
+                field: Some(missing.to_string()),
+                kind: ParseErrorKind::MissingField,
+            });
+        }
+
+        Ok(self.format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::card_database::SAMPLE_CARDS;
+    use crate::data::deck_list::SAMPLE_DECK;
+
+    fn sample() -> (GameData, DeckList) {
+        let data = GameData::parse(SAMPLE_CARDS).unwrap();
+        let deck = DeckList::parse(SAMPLE_DECK, &data).unwrap();
+        (data, deck)
+    }
+
+    fn format(kind: FormatKind, titles: &[&str]) -> Format {
+        Format {
+            name: "Test".to_string(),
+            kind,
+            titles: titles.iter().map(|title| title.to_string()).collect(),
+            sets: Vec::new(),
+            banned: Vec::new(),
+            restricted: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn sample_formats() {
+        let (data, deck) = sample();
+        let formats = Format::parse_all(SAMPLE_FORMATS).unwrap();
+
+        let reports = check_formats(&deck, &data, &formats);
+
+        assert_eq!(reports.len(), 3);
+        assert!(reports[0].is_legal());
+        assert_eq!(
+            reports[1].violations,
+            vec![
+                FormatViolation::Banned {
+                    code: "SMP/S01-010".to_string()
+                },
+                FormatViolation::Restricted {
+                    codes: vec!["SMP/S01-006".to_string(), "SMP/S01-008".to_string()]
+                },
+            ]
+        );
+        assert_eq!(reports[2].violations.len(), deck.cards.len());
+    }
+
+    #[test]
+    fn allows() {
+        let (data, _) = sample();
+        let card = data.card(0.into());
+        let mut format = format(FormatKind::NeoStandard, &["OTH"]);
+
+        assert!(!format.allows(card));
+
+        format.sets.push("S01".to_string());
+
+        assert!(format.allows(card));
+    }
+
+    #[test]
+    fn check_includes_construction() {
+        let data = GameData::parse(SAMPLE_CARDS).unwrap();
+        let text = format!("{}\nSMP/S01-001 1\n", SAMPLE_DECK);
+        let deck = DeckList::parse(&text, &data).unwrap();
+        let mut format = format(FormatKind::TitleSpecific, &["SMP"]);
+        format
+            .restricted
+            .push(vec!["SMP/S01-001".to_string(), "SMP/S01-999".to_string()]);
+
+        let report = format.check(&deck, &data);
+
+        assert!(!report.is_legal());
+        assert_eq!(
+            report.violations,
+            vec![
+                FormatViolation::Construction(DeckError::WrongSize { count: 51 }),
+                FormatViolation::Construction(DeckError::TooManyCopies {
+                    name: data.cards[0].name.clone(),
+                    count: 5
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_title_specific() {
+        let (mut data, deck) = sample();
+        let formats = Format::parse_all(
+            "[format]\nname = Mixed\nkind = neo-standard\ntitles = SMP, OTH\n\n\
+             [format]\nname = Single\nkind = title-specific\ntitles = SMP, OTH\n",
+        )
+        .unwrap();
+
+        assert!(formats[1].check(&deck, &data).is_legal());
+
+        data.cards[0].title = "OTH".to_string();
+
+        assert!(formats[0].check(&deck, &data).is_legal());
+        assert_eq!(
+            formats[1].check(&deck, &data).violations,
+            vec![FormatViolation::MixedTitles {
+                titles: vec!["SMP".to_string(), "OTH".to_string()]
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text| Format::parse_all(text).unwrap_err();
+
+        assert_eq!(
+            error("[format]\nname = A\nkind = casual\n"),
+            ParseError {
+                line: 3,
+                field: Some("kind".to_string()),
+                kind: ParseErrorKind::InvalidValue {
+                    value: "casual".to_string(),
+                    expected: "`neo-standard` or `title-specific`",
+                },
+            }
+        );
+        assert_eq!(
+            error("[format]\nname = A\nkind = neo-standard\n"),
+            ParseError {
+                line: 1,
+                field: Some("titles".to_string()),
+                kind: ParseErrorKind::MissingField,
+            }
+        );
+        assert_eq!(
+            error("[format]\nname = A\nkind = neo-standard\ntitles = ,\n").to_string(),
+            "line 4, field `titles`: expected a list of titles, found `,`"
+        );
+        assert_eq!(
+            error("[format]\nrestricted = SMP/S01-001\n")
+                .field
+                .as_deref(),
+            Some("restricted")
+        );
+    }
+
+    #[test]
+    fn load_all() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/sample_formats.txt");
+
+        assert_eq!(
+            Format::load_all(path).unwrap(),
+            Format::parse_all(SAMPLE_FORMATS).unwrap()
+        );
+    }
+}
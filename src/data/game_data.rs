@@ -1,3 +1,137 @@
+use crate::data::card_data::CardData;
+use crate::data::card_database::{self, ParseError};
+use crate::data::CardDataId;
+use std::fmt;
+use std::path::Path;
+
+/// An error loading a data file, such as a card database.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The file's contents are invalid.
+    Parse(ParseError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "couldn't read file: {}", error),
+            LoadError::Parse(error) => write!(f, "invalid data: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<std::io::Error> for LoadError {
+    fn from(error: std::io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(error: ParseError) -> Self {
+        LoadError::Parse(error)
+    }
+}
+
 /// Contains all the static data for cards and abilities that exist in a rules-managed game.
 #[derive(Debug)]
-pub struct GameData {}
+pub struct GameData {
+    pub cards: Vec<CardData>,
+}
+
+impl GameData {
+    /// Creates a GameData with the `cards`, where each card's
+    /// id is its index.
+    pub fn new(cards: Vec<CardData>) -> Self {
+        Self { cards }
+    }
+
+    /// Creates a GameData from the text of a card database.
+    /// See [`card_database::parse_cards`] for the format.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        card_database::parse_cards(text).map(Self::new)
+    }
+
+    /// Creates a GameData from the card database file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Self::parse(&text)?)
+    }
+
+    /// Returns the id of the card with the `code`, if there is one.
+    pub fn find_code(&self, code: &str) -> Option<CardDataId> {
+        self.cards
+            .iter()
+            .position(|card| card.code == code)
+            .map(CardDataId)
+    }
+
+    /// Creates a GameData with `count` default cards.
+    pub fn with_default_cards(count: usize) -> Self {
+        Self {
+            cards: vec![CardData::default(); count],
+        }
+    }
+
+    /// Returns the data for the `card`.
+    ///
+    /// Precondition: `card` must exist in this GameData.
+    pub fn card(&self, card: CardDataId) -> &CardData {
+        &self.cards[card.0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::card_data::{CardType, Color};
+
+    #[test]
+    fn card() {
+        let climax = CardData {
+            name: "Climax".to_string(),
+            card_type: CardType::Climax,
+            ..Default::default()
+        };
+        let mut character = CardData {
+            name: "Character".to_string(),
+            traits: vec!["Music".to_string()],
+            ..Default::default()
+        };
+        character.base_stats.color = Color::Red;
+
+        let data = GameData::new(vec![climax.clone(), character.clone()]);
+
+        assert_eq!(data.card(0.into()), &climax);
+        assert_eq!(data.card(1.into()), &character);
+        assert_eq!(data.card(1.into()).base_stats.power, 3000);
+    }
+
+    #[test]
+    fn load() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/sample_cards.txt");
+        let data = GameData::load(path).unwrap();
+
+        assert_eq!(
+            data.cards,
+            GameData::parse(card_database::SAMPLE_CARDS).unwrap().cards
+        );
+        assert_eq!(data.card(0.into()).name, "Morning Practice Drummer");
+        assert_eq!(data.card(14.into()).card_type, CardType::Climax);
+        assert_eq!(data.find_code("SMP/S01-003"), Some(2.into()));
+        assert_eq!(data.find_code("SMP/S01-999"), None);
+    }
+
+    #[test]
+    fn load_errors() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/missing.txt");
+        assert!(matches!(GameData::load(path), Err(LoadError::Io(_))));
+
+        let error = GameData::parse("[card]\ncode = A\nname = A\ntype = spell\n").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.field.as_deref(), Some("type"));
+    }
+}
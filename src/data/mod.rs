@@ -1,15 +1,20 @@
+pub mod card_data;
+pub mod card_database;
+pub mod cost;
+pub mod deck_list;
+pub mod format;
 pub mod game_data;
 mod phase;
 
 pub use phase::Phase;
 
-/// A lightweight ID used to identify unique cards in a game state, and it's associated game data.
+/// A lightweight ID used to identify a unique physical card in a game state.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub struct CardId(pub usize);
 
-impl Into<CardId> for usize {
-    fn into(self) -> CardId {
-        CardId(self)
+impl From<usize> for CardId {
+    fn from(id: usize) -> Self {
+        CardId(id)
     }
 }
 
@@ -18,3 +23,20 @@ impl std::fmt::Display for CardId {
         write!(f, "{}", self.0,)
     }
 }
+
+/// A lightweight ID used to identify a card's definition in game data.  Every
+/// copy of a card in a game shares the same CardDataId.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CardDataId(pub usize);
+
+impl From<usize> for CardDataId {
+    fn from(id: usize) -> Self {
+        CardDataId(id)
+    }
+}
+
+impl std::fmt::Display for CardDataId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0,)
+    }
+}
@@ -5,4 +5,7 @@ pub enum Phase {
     End,
     Draw,
     Clock,
+    Main,
+    Climax,
+    Attack,
 }
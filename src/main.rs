@@ -1,9 +1,16 @@
 use std::io::*;
 use std::str::FromStr;
+use ws_engine::data::card_data::TriggerIcon;
+use ws_engine::data::cost::Cost;
 use ws_engine::data::{CardId, Phase};
-use ws_engine::rules::io::{ChoiceContext, Input, InterruptChoice, Output, RulesEngineIO};
+use ws_engine::rules::io::{
+    AttackType, ChoiceContext, ChoiceTriggerDestination, Input, MainPhaseAction, Output,
+    PendingAction, RulesEngineIO, TurnOrder,
+};
 use ws_engine::rules::Rules;
-use ws_engine::state::player_state::LevelUpResult;
+use ws_engine::state::player_state::{DamageResult, LevelUpResult, TriggeredAbility};
+use ws_engine::state::rng::Rng;
+use ws_engine::state::stage_state::StagePosition;
 
 #[derive(Debug)]
 struct BasicIO;
@@ -15,35 +22,108 @@ impl BasicIO {
         context: ChoiceContext,
         optional: bool,
     ) -> String {
-        match context {
-            ChoiceContext::ClockPhaseCardToClock => format!(
-                "player {} {} choose to clock from: {:?}",
-                choosing_player,
-                if optional { "may" } else { "must" },
-                options
-            ),
-            ChoiceContext::HandLimitDiscard => format!(
-                "player {} {} choose to discard one from: {:?}",
-                choosing_player,
-                if optional { "may" } else { "must" },
-                options
-            ),
-            ChoiceContext::LevelUpProcess => format!(
-                "player {} {} choose from clock to level up with: {:?}",
-                choosing_player,
-                if optional { "may" } else { "must" },
-                options
-            ),
-            ChoiceContext::InterruptTimingChoice => format!(
-                "player {} {}  chooses which to process first among: {:?}",
-                choosing_player,
-                if optional { "may" } else { "must" },
-                options
-            ),
-        }
+        let request = match context {
+            ChoiceContext::ClockPhaseCardToClock => "choose to clock from",
+            ChoiceContext::HandLimitDiscard => "choose to discard one from",
+            ChoiceContext::LevelUpProcess => "choose from clock to level up with",
+            ChoiceContext::InterruptTimingChoice => "choose which to process first by index from",
+            ChoiceContext::MainPhaseAction => "choose an action by index from",
+            ChoiceContext::MainPhaseCharacterPosition => "choose a stage position by index from",
+            ChoiceContext::ClimaxPhaseCardToPlay => "choose a climax to play from",
+            ChoiceContext::AttackDeclarationAttacker => "choose an attacker by index from",
+            ChoiceContext::AttackDeclarationAttackType => "choose an attack type by index from",
+            ChoiceContext::TriggerStepUseEffect => "choose to use the trigger effect by index from",
+            ChoiceContext::TriggerStepBounceTarget => "choose a character to bounce by index from",
+            ChoiceContext::TriggerStepGateTarget => "choose a climax to return to hand from",
+            ChoiceContext::TriggerStepDoorTarget => "choose a character to return to hand from",
+            ChoiceContext::TriggerStepChoiceTarget => "choose a character to return from",
+            ChoiceContext::TriggerStepChoiceDestination => "choose where to put it by index from",
+            ChoiceContext::TriggerStepStandbyTarget => "choose a character to put on stage from",
+            ChoiceContext::TriggerStepStandbyPosition => "choose a stage position by index from",
+            ChoiceContext::EncoreStepCharacter => "choose a reversed character by index from",
+            ChoiceContext::EncoreStepCost => "choose an encore cost by index from",
+            ChoiceContext::PayCostDiscard => "choose a card to discard from",
+            ChoiceContext::PayCostDiscardCharacter => "choose a character to discard from",
+            ChoiceContext::CheckTimingChoice => "choose which to perform first by index from",
+            ChoiceContext::GameStartTurnOrder => "choose to go first or second by index from",
+            ChoiceContext::MulliganDiscard => "choose a card to mulligan from",
+            ChoiceContext::CounterStepCard => "choose a counter card to play from",
+            ChoiceContext::MainPhaseMoveDestination => "choose where to move by index from",
+        };
+
+        format!(
+            "player {} {} {}: {:?}",
+            choosing_player,
+            if optional { "may" } else { "must" },
+            request,
+            options
+        )
+    }
+
+    /// Reads an index into a list of `len` options, or None
+    /// if the line read is not a valid index.
+    fn read_index(len: usize) -> Option<usize> {
+        let mut choice_buffer = String::new();
+
+        let _ = stdout().flush();
+        let _ = stdin().read_line(&mut choice_buffer);
+
+        usize::from_str(choice_buffer.trim())
+            .ok()
+            .filter(|index| *index < len)
     }
 }
 
+/// Implements `Input` for options that are chosen by their index.
+macro_rules! index_input {
+    ($($option:ty),*) => {
+        $(
+            impl Input<$option> for BasicIO {
+                fn ask_optional_choice(
+                    &mut self,
+                    options: &[$option],
+                    choosing_player: usize,
+                    context: ChoiceContext,
+                ) -> Option<usize> {
+                    println!(
+                        "{}",
+                        BasicIO::get_message(options, choosing_player, context, true)
+                    );
+                    BasicIO::read_index(options.len())
+                }
+
+                fn ask_required_choice(
+                    &mut self,
+                    options: &[$option],
+                    choosing_player: usize,
+                    context: ChoiceContext,
+                ) -> usize {
+                    println!(
+                        "{}",
+                        BasicIO::get_message(options, choosing_player, context, false)
+                    );
+                    loop {
+                        if let Some(index) = BasicIO::read_index(options.len()) {
+                            break index;
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+index_input!(
+    MainPhaseAction,
+    StagePosition,
+    AttackType,
+    TriggerIcon,
+    ChoiceTriggerDestination,
+    Vec<Cost>,
+    PendingAction,
+    TurnOrder
+);
+
 impl Output for BasicIO {
     fn phase_change(&mut self, phase: Phase, turn_player: usize) {
         println!("Phase Changed: {:?} for player {}", phase, turn_player);
@@ -65,73 +145,134 @@ impl Output for BasicIO {
         println!("player {} clocked card {}", turn_player, card);
     }
 
+    fn shuffle(&mut self, player: usize) {
+        println!("player {} shuffled their deck", player);
+    }
+
+    fn first_player(&mut self, player: usize) {
+        println!("player {} goes first", player);
+    }
+
+    fn mulligan(&mut self, cards: Vec<CardId>, player: usize) {
+        println!("player {} mulliganed {:?}", player, cards);
+    }
+
     fn refreshed(&mut self, turn_player: usize) {
         println!("player {} refreshed", turn_player,);
     }
-}
 
-impl Input<InterruptChoice> for BasicIO {
-    fn ask_optional_choice(
-        &mut self,
-        options: &[InterruptChoice],
-        choosing_player: usize,
-        context: ChoiceContext,
-    ) -> Option<usize> {
+    fn refresh_damage(&mut self, card: CardId, turn_player: usize) {
         println!(
-            "{}",
-            BasicIO::get_message(options, choosing_player, context, true)
+            "player {} took refresh damage with card {}",
+            turn_player, card
         );
-        let mut choice_buffer = String::new();
+    }
 
-        let _ = stdout().flush();
-        let _ = stdin().read_line(&mut choice_buffer);
+    fn resolve_ability(&mut self, ability: TriggeredAbility, turn_player: usize) {
+        println!(
+            "player {} resolved ability {} of card {}",
+            turn_player, ability.ability, ability.source
+        );
+    }
 
-        let choice_buffer = choice_buffer.trim();
+    fn play_character(&mut self, card: CardId, position: StagePosition, turn_player: usize) {
+        println!(
+            "player {} played character {} to {:?}",
+            turn_player, card, position
+        );
+    }
 
-        match choice_buffer {
-            "level" => options
-                .iter()
-                .position(|item| *item == InterruptChoice::Level),
-            "refresh" => options
-                .iter()
-                .position(|item| *item == InterruptChoice::Refresh),
-            _ => None,
-        }
+    fn move_character(&mut self, from: StagePosition, to: StagePosition, turn_player: usize) {
+        println!(
+            "player {} moved character at {:?} to {:?}",
+            turn_player, from, to
+        );
     }
 
-    fn ask_required_choice(
+    fn play_event(&mut self, card: CardId, turn_player: usize) {
+        println!("player {} played event {}", turn_player, card);
+    }
+
+    fn event_resolved(&mut self, card: CardId, turn_player: usize) {
+        println!("player {}'s event {} resolved", turn_player, card);
+    }
+
+    fn play_climax(&mut self, card: CardId, turn_player: usize) {
+        println!("player {} played climax {}", turn_player, card);
+    }
+
+    fn declare_attack(
         &mut self,
-        options: &[InterruptChoice],
-        choosing_player: usize,
-        context: ChoiceContext,
-    ) -> usize {
+        attacker: StagePosition,
+        attack_type: AttackType,
+        turn_player: usize,
+    ) {
         println!(
-            "{}",
-            BasicIO::get_message(options, choosing_player, context, false)
+            "player {} declared a {:?} attack with {:?}",
+            turn_player, attack_type, attacker
         );
-        let mut choice_buffer = String::new();
+    }
 
-        let _ = stdout().flush();
-        let _ = stdin().read_line(&mut choice_buffer);
+    fn trigger(&mut self, card: CardId, turn_player: usize) {
+        println!("player {} revealed trigger card {}", turn_player, card);
+    }
 
-        loop {
-            let _ = stdout().flush();
-            let _ = stdin().read_line(&mut choice_buffer);
-            match choice_buffer.trim() {
-                "level" => {
-                    break options
-                        .iter()
-                        .position(|item| *item == InterruptChoice::Level)
-                        .unwrap()
-                }
-                "refresh" => {
-                    break options
-                        .iter()
-                        .position(|item| *item == InterruptChoice::Refresh)
-                        .unwrap()
-                }
-                _ => (),
-            }
+    fn trigger_effect(&mut self, icon: TriggerIcon, card: Option<CardId>, turn_player: usize) {
+        match card {
+            Some(card) => println!(
+                "player {} resolved a {:?} trigger with card {}",
+                turn_player, icon, card
+            ),
+            None => println!("player {} resolved a {:?} trigger", turn_player, icon),
+        }
+    }
+
+    fn damage_revealed(&mut self, card: CardId, turn_player: usize) {
+        println!("player {} revealed damage card {}", turn_player, card);
+    }
+
+    fn damage(&mut self, result: DamageResult, turn_player: usize) {
+        println!("player {} took damage with {:?}", turn_player, result);
+    }
+
+    fn stand(&mut self, position: StagePosition, turn_player: usize) {
+        println!("player {}'s character at {:?} stood", turn_player, position);
+    }
+
+    fn counter(&mut self, card: CardId, position: StagePosition, player: usize) {
+        println!(
+            "player {} countered with {} for their character at {:?}",
+            player, card, position
+        );
+    }
+
+    fn reverse(&mut self, position: StagePosition, turn_player: usize) {
+        println!(
+            "player {}'s character at {:?} reversed",
+            turn_player, position
+        );
+    }
+
+    fn pay_cost(&mut self, cost: Cost, cards: Vec<CardId>, turn_player: usize) {
+        println!("player {} paid {:?} with {:?}", turn_player, cost, cards);
+    }
+
+    fn encore(
+        &mut self,
+        card: CardId,
+        position: StagePosition,
+        cost: Option<Vec<Cost>>,
+        turn_player: usize,
+    ) {
+        match cost {
+            Some(cost) => println!(
+                "player {} encored {} to {:?} with {:?}",
+                turn_player, card, position, cost
+            ),
+            None => println!(
+                "player {}'s character {} at {:?} was put into the waiting room",
+                turn_player, card, position
+            ),
         }
     }
 }
@@ -154,10 +295,10 @@ impl Input<CardId> for BasicIO {
 
         let choice_buffer = choice_buffer.trim();
 
-        if choice_buffer == "" {
+        if choice_buffer.is_empty() {
             None
         } else {
-            let id = usize::from_str(&choice_buffer).ok()?.into();
+            let id = usize::from_str(choice_buffer).ok()?.into();
             options.iter().position(|item| *item == id)
         }
     }
@@ -179,12 +320,12 @@ impl Input<CardId> for BasicIO {
 
         let mut choice = choice_buffer.trim();
 
-        while usize::from_str(&choice).is_err() {
+        while usize::from_str(choice).is_err() {
             let _ = stdout().flush();
             let _ = stdin().read_line(&mut choice_buffer);
             choice = choice_buffer.trim();
         }
-        let id = usize::from_str(&choice).unwrap().into();
+        let id = usize::from_str(choice).unwrap().into();
         options.iter().position(|item| *item == id).unwrap()
     }
 }
@@ -193,11 +334,12 @@ impl RulesEngineIO for BasicIO {}
 
 fn main() {
     let mut io = BasicIO;
-    let mut engine = Rules::new();
+    let seed = Rng::random_seed();
+    println!("seed: {}", seed);
+    let mut engine = Rules::with_seed(seed);
 
-    for _ in 0..50 {
-        engine.run_turn(&mut io);
+    let result = engine.run_game(&mut io, None);
 
-        println!("{:?}", engine);
-    }
+    println!("{:?}", engine);
+    println!("game over: {:?}", result);
 }
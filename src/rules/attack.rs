@@ -0,0 +1,564 @@
+use super::io::{AttackType, ChoiceContext, RulesEngineIO};
+use super::{GameResult, Rules};
+use crate::data::Phase;
+use crate::state::stage_state::{CharacterStatus, StageCharacter, StagePosition};
+
+/// The state of an attack that is being processed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) struct Attack {
+    pub attacker: StagePosition,
+    pub attack_type: AttackType,
+    /// Whether a shot trigger was revealed during this attack.
+    pub shot: bool,
+}
+
+impl Rules {
+    /// Processes the attack phase.
+    ///
+    /// 1) Publishes a Phase change with the value `Phase::Attack`
+    ///
+    /// 2) Asks the active player to choose a standing center stage
+    ///    character to attack with, or no character.
+    ///
+    /// 3) If a character is chosen, declares an attack with it.
+    ///
+    /// 4) Repeats 2) until the active player has no characters
+    ///    left to attack with, chooses to stop, or has made as many
+    ///    attacks as they are allowed this turn.
+    ///
+    /// 5) Processes the encore step.
+    pub(super) fn attack_phase<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
+        self.phase_change(io, Phase::Attack);
+
+        let player = self.state.active_player;
+        let mut attacks = 0;
+
+        while self.attack_limit().is_none_or(|limit| attacks < limit) {
+            let attackers = self.available_attackers(player);
+            if attackers.is_empty() {
+                break;
+            }
+
+            let attacker = io.ask_optional_choice(
+                &attackers,
+                player,
+                ChoiceContext::AttackDeclarationAttacker,
+            );
+
+            let attacker = match attacker {
+                Some(attacker) => attackers[attacker],
+                None => break,
+            };
+
+            self.attack(io, attacker)?;
+
+            attacks += 1;
+        }
+
+        self.encore_step(io)?;
+
+        Ok(())
+    }
+
+    /// Processes one attack by the character in `attacker`.
+    ///
+    /// 1) Declares the attack.
+    ///
+    /// 2) Processes the trigger step.
+    ///
+    /// 3) Processes the counter step, if it was a front attack.
+    ///
+    /// 4) Processes the damage step.
+    ///
+    /// 5) Processes the battle step, if it was a front attack.
+    ///
+    /// A check timing is processed after each step.
+    fn attack<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        attacker: StagePosition,
+    ) -> Result<(), GameResult> {
+        let attack_type = self.declare_attack(io, attacker);
+
+        let mut attack = Attack {
+            attacker,
+            attack_type,
+            shot: false,
+        };
+
+        self.trigger_step(io, &mut attack)?;
+        self.check_timing(io)?;
+
+        if attack.attack_type == AttackType::Front {
+            self.counter_step(io, &attack)?;
+            self.check_timing(io)?;
+        }
+
+        self.damage_step(io, &attack)?;
+        self.check_timing(io)?;
+
+        if attack.attack_type == AttackType::Front {
+            self.battle_step(io, &attack);
+            self.check_timing(io)?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes the damage step of the `attack`.
+    ///
+    /// Deals damage equal to the attacker's soul for the attack to the
+    /// non active player.
+    /// If that damage was cancelled and a shot trigger was revealed during
+    /// the attack, 1 more damage is dealt.
+    fn damage_step<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        attack: &Attack,
+    ) -> Result<(), GameResult> {
+        let defender = self.state.non_active_player();
+        let amount = self.attack_soul(attack.attacker, attack.attack_type);
+
+        let result = self.deal_damage(io, defender, amount)?;
+
+        if result.cancelled && attack.shot {
+            self.deal_damage(io, defender, 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes the battle step of the `attack`.
+    ///
+    /// Compares the current power of the attacker and the character
+    /// in front of it.  The character with lower power is reversed,
+    /// and if they are tied both are reversed.  A reverse event is
+    /// published for each reversed character.
+    ///
+    /// If either character is no longer on the stage, there is no battle.
+    fn battle_step<T: RulesEngineIO>(&mut self, io: &mut T, attack: &Attack) {
+        let player = self.state.active_player;
+        let defender = self.state.non_active_player();
+        let defending = attack
+            .attacker
+            .opposing()
+            .expect("only center stage characters can attack");
+
+        let attacker_power = match self.state.players[player].stage.get(attack.attacker) {
+            Some(character) => self.character_power(character),
+            None => return,
+        };
+        let defender_power = match self.state.players[defender].stage.get(defending) {
+            Some(character) => self.character_power(character),
+            None => return,
+        };
+
+        if attacker_power <= defender_power {
+            self.state.players[player]
+                .stage
+                .reverse(attack.attacker)
+                .unwrap();
+            io.reverse(attack.attacker, player);
+        }
+        if defender_power <= attacker_power {
+            self.state.players[defender]
+                .stage
+                .reverse(defending)
+                .unwrap();
+            io.reverse(defending, defender);
+        }
+    }
+
+    /// Returns the current power of the `character`.
+    pub(super) fn character_power(&self, character: &StageCharacter) -> i32 {
+        self.card_data(character.card).base_stats.power + character.turn_modifiers.power
+    }
+
+    /// Returns the amount of damage the active player's character in
+    /// `attacker` would deal with an attack of `attack_type`.  This can be
+    /// used to preview damage before an attack is declared.
+    ///
+    /// This is the character's current soul, including any soul triggers,
+    /// plus 1 for a direct attack, or minus the level of the character in
+    /// front of it for a side attack, to a minimum of 0.  If there is no
+    /// character in `attacker`, this is 0.
+    pub fn attack_soul(&self, attacker: StagePosition, attack_type: AttackType) -> i32 {
+        let character = match self.active_player().stage.get(attacker) {
+            Some(character) => character,
+            None => return 0,
+        };
+        let soul = self.card_data(character.card).base_stats.soul + character.turn_modifiers.soul;
+
+        let modifier = match attack_type {
+            AttackType::Front => 0,
+            AttackType::Direct => 1,
+            AttackType::Side => attacker
+                .opposing()
+                .and_then(|defending| {
+                    self.state.players[self.state.non_active_player()]
+                        .stage
+                        .get(defending)
+                })
+                .map_or(0, |defending| {
+                    -self.card_data(defending.card).base_stats.level
+                }),
+        };
+
+        (soul + modifier).max(0)
+    }
+
+    /// Returns the maximum number of attacks the active player
+    /// can make this turn, if there is one.
+    ///
+    /// The first player can only attack once on their first turn.
+    fn attack_limit(&self) -> Option<usize> {
+        if self.state.turn == 0 && self.state.active_player == self.state.first_player {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns every center stage position of the `player` that
+    /// has a standing character that can attack.
+    fn available_attackers(&self, player: usize) -> Vec<StagePosition> {
+        let stage = &self.state.players[player].stage;
+
+        StagePosition::CENTER
+            .iter()
+            .filter(|position| {
+                stage
+                    .get(**position)
+                    .is_some_and(|character| character.status == CharacterStatus::Standing)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Declares an attack for the active player with the character in `attacker`.
+    ///
+    /// Precondition: `attacker` is a standing center stage character of the active player.
+    ///
+    /// Postcondition: The attacking character is rested and a declare attack
+    /// event is published.  If there is a character in front of the attacker,
+    /// the active player chooses a front or side attack, otherwise the
+    /// attack is a direct attack.
+    ///
+    /// Returns the kind of attack that was declared.
+    fn declare_attack<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        attacker: StagePosition,
+    ) -> AttackType {
+        let player = self.state.active_player;
+        let defender = self.state.non_active_player();
+
+        self.state.players[player].stage.rest(attacker).unwrap();
+
+        let opposing = attacker
+            .opposing()
+            .expect("only center stage characters can attack");
+
+        let attack_type = if self.state.players[defender].stage.is_empty(opposing) {
+            AttackType::Direct
+        } else {
+            let options = [AttackType::Front, AttackType::Side];
+            let choice = io.ask_required_choice(
+                &options,
+                player,
+                ChoiceContext::AttackDeclarationAttackType,
+            );
+            options[choice]
+        };
+
+        io.declare_attack(attacker, attack_type, player);
+
+        attack_type
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::io::{EventLog, PickX};
+
+    #[test]
+    fn available_attackers() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.new_character(player, StagePosition::CenterLeft);
+        rules.new_character(player, StagePosition::CenterMiddle);
+        rules.new_character(player, StagePosition::BackLeft);
+        rules
+            .active_player_mut()
+            .stage
+            .rest(StagePosition::CenterMiddle)
+            .unwrap();
+
+        assert_eq!(
+            rules.available_attackers(player),
+            vec![StagePosition::CenterLeft]
+        );
+    }
+
+    #[test]
+    fn declare_direct_attack() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.new_character(player, StagePosition::CenterLeft);
+
+        assert_eq!(
+            rules.declare_attack(&mut (), StagePosition::CenterLeft),
+            AttackType::Direct
+        );
+        assert_eq!(
+            rules
+                .active_player()
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .status,
+            CharacterStatus::Rested
+        );
+    }
+
+    #[test]
+    fn declare_front_and_side_attack() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let defender = rules.state.non_active_player();
+
+        rules.new_character(player, StagePosition::CenterLeft);
+        rules.new_character(player, StagePosition::CenterRight);
+        rules.new_character(defender, StagePosition::CenterLeft);
+        rules.new_character(defender, StagePosition::CenterRight);
+
+        assert_eq!(
+            rules.declare_attack(&mut (), StagePosition::CenterLeft),
+            AttackType::Front
+        );
+        assert_eq!(
+            rules.declare_attack(&mut PickX(1), StagePosition::CenterRight),
+            AttackType::Side
+        );
+    }
+
+    #[test]
+    fn damage_step_shot() {
+        use crate::data::card_data::CardType;
+
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let defender = rules.state.non_active_player();
+
+        rules.new_character(player, StagePosition::CenterLeft);
+        let deck: Vec<_> = (0..3).map(|_| rules.new_card(defender)).collect();
+        rules.card_data_mut(deck[2]).card_type = CardType::Climax;
+        rules.state.players[defender].deck.content = deck.clone();
+
+        let attack = Attack {
+            attacker: StagePosition::CenterLeft,
+            attack_type: AttackType::Direct,
+            shot: true,
+        };
+        rules.damage_step(&mut (), &attack).unwrap();
+
+        assert_eq!(
+            rules.state.players[defender].waiting_room.content,
+            vec![deck[2]]
+        );
+        assert_eq!(rules.state.players[defender].clock.content, vec![deck[1]]);
+    }
+
+    #[test]
+    fn attack_soul() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let defender = rules.state.non_active_player();
+
+        assert_eq!(
+            rules.attack_soul(StagePosition::CenterLeft, AttackType::Direct),
+            0
+        );
+
+        let attacker = rules.new_character(player, StagePosition::CenterLeft);
+        rules.card_data_mut(attacker).base_stats.soul = 2;
+
+        assert_eq!(
+            rules.attack_soul(StagePosition::CenterLeft, AttackType::Direct),
+            3
+        );
+
+        let defending = rules.new_character(defender, StagePosition::CenterRight);
+        rules.card_data_mut(defending).base_stats.level = 1;
+
+        assert_eq!(
+            rules.attack_soul(StagePosition::CenterLeft, AttackType::Front),
+            2
+        );
+        assert_eq!(
+            rules.attack_soul(StagePosition::CenterLeft, AttackType::Side),
+            1
+        );
+
+        rules
+            .active_player_mut()
+            .stage
+            .get_mut(StagePosition::CenterLeft)
+            .unwrap()
+            .turn_modifiers
+            .soul += 1;
+
+        assert_eq!(
+            rules.attack_soul(StagePosition::CenterLeft, AttackType::Side),
+            2
+        );
+
+        rules.state.players[defender]
+            .stage
+            .take_from_slot(StagePosition::CenterRight);
+        let defending = rules.new_character(defender, StagePosition::CenterRight);
+        rules.card_data_mut(defending).base_stats.level = 3;
+
+        assert_eq!(
+            rules.attack_soul(StagePosition::CenterLeft, AttackType::Side),
+            0
+        );
+    }
+
+    #[test]
+    fn damage_step_no_soul() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let defender = rules.state.non_active_player();
+        rules.new_character(player, StagePosition::CenterLeft);
+        let defending = rules.new_character(defender, StagePosition::CenterRight);
+        rules.card_data_mut(defending).base_stats.level = 1;
+        let attack = Attack {
+            attacker: StagePosition::CenterLeft,
+            attack_type: AttackType::Side,
+            shot: false,
+        };
+
+        let mut io = EventLog::default();
+        rules.damage_step(&mut io, &attack).unwrap();
+
+        assert!(io.named("damage").is_empty());
+        assert!(io.named("damage_revealed").is_empty());
+        assert!(rules.state.players[defender].clock.content.is_empty());
+    }
+
+    fn battle_rules(attacker_power: i32, defender_power: i32) -> Rules {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let defender = rules.state.non_active_player();
+
+        let attacker = rules.new_character(player, StagePosition::CenterLeft);
+        rules.card_data_mut(attacker).base_stats.power = attacker_power;
+        let defending = rules.new_character(defender, StagePosition::CenterRight);
+        rules.card_data_mut(defending).base_stats.power = defender_power;
+
+        rules
+    }
+    fn battle_result(
+        attacker_power: i32,
+        defender_power: i32,
+    ) -> (CharacterStatus, CharacterStatus) {
+        let mut rules = battle_rules(attacker_power, defender_power);
+        let defender = rules.state.non_active_player();
+
+        let attack = Attack {
+            attacker: StagePosition::CenterLeft,
+            attack_type: AttackType::Front,
+            shot: false,
+        };
+        rules.battle_step(&mut (), &attack);
+
+        (
+            rules
+                .active_player()
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .status,
+            rules.state.players[defender]
+                .stage
+                .get(StagePosition::CenterRight)
+                .unwrap()
+                .status,
+        )
+    }
+
+    #[test]
+    fn battle_step() {
+        assert_eq!(
+            battle_result(3000, 2500),
+            (CharacterStatus::Standing, CharacterStatus::Reversed)
+        );
+        assert_eq!(
+            battle_result(2500, 3000),
+            (CharacterStatus::Reversed, CharacterStatus::Standing)
+        );
+        assert_eq!(
+            battle_result(3000, 3000),
+            (CharacterStatus::Reversed, CharacterStatus::Reversed)
+        );
+    }
+
+    #[test]
+    fn front_attack_battles() {
+        let mut rules = battle_rules(3000, 2500);
+        let defender = rules.state.non_active_player();
+
+        rules.attack(&mut (), StagePosition::CenterLeft).unwrap();
+
+        assert_eq!(
+            rules.state.players[defender]
+                .stage
+                .get(StagePosition::CenterRight)
+                .unwrap()
+                .status,
+            CharacterStatus::Reversed
+        );
+
+        let mut rules = battle_rules(3000, 2500);
+        let defender = rules.state.non_active_player();
+
+        rules
+            .attack(&mut PickX(1), StagePosition::CenterLeft)
+            .unwrap();
+
+        assert_eq!(
+            rules.state.players[defender]
+                .stage
+                .get(StagePosition::CenterRight)
+                .unwrap()
+                .status,
+            CharacterStatus::Standing
+        );
+    }
+
+    #[test]
+    fn first_turn_single_attack() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        for position in StagePosition::CENTER.iter() {
+            rules.new_character(player, *position);
+        }
+
+        rules.attack_phase(&mut ()).unwrap();
+
+        assert_eq!(rules.state.phase, Phase::Attack);
+        assert_eq!(rules.available_attackers(0).len(), 2);
+
+        rules.switch_turns();
+        rules.switch_turns();
+
+        rules.attack_phase(&mut ()).unwrap();
+
+        assert!(rules.available_attackers(0).is_empty());
+    }
+}
@@ -0,0 +1,533 @@
+use super::io::{ChoiceContext, PendingAction, RulesEngineIO};
+use super::{GameResult, LossReason, Rules};
+use crate::data::card_data::{Ability, Trigger};
+use crate::data::CardId;
+use crate::state::game_state::GameState;
+use crate::state::player_state::TriggeredAbility;
+use std::fmt;
+use std::rc::Rc;
+
+/// An action the rules perform on their own whenever its condition
+/// is met.  The actions checked are the ones registered on the [`Rules`],
+/// so new ones can be added with [`Rules::add_rule_action`].
+pub trait RuleAction: fmt::Debug {
+    /// A short name for the action, for players choosing which
+    /// pending action to perform first.
+    fn name(&self) -> &'static str;
+
+    /// Checks to see if this is an interrupt-type rule action, which
+    /// is performed as soon as its condition is met rather than
+    /// waiting for a check timing.
+    fn is_interrupt(&self) -> bool;
+
+    /// Checks to see if the condition for this action is met for the `player`.
+    fn pending(&self, state: &GameState, player: usize) -> bool;
+
+    /// Performs this action for the `player`.
+    ///
+    /// Returns the reason the `player` lost, if they did.
+    fn perform(
+        &self,
+        rules: &mut Rules,
+        io: &mut dyn RulesEngineIO,
+        player: usize,
+    ) -> Option<LossReason>;
+}
+
+/// The player has 7 or more cards in their clock.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Level;
+
+impl RuleAction for Level {
+    fn name(&self) -> &'static str {
+        "level"
+    }
+
+    fn is_interrupt(&self) -> bool {
+        true
+    }
+
+    fn pending(&self, state: &GameState, player: usize) -> bool {
+        state.players[player].needs_to_level()
+    }
+
+    fn perform(
+        &self,
+        rules: &mut Rules,
+        io: &mut dyn RulesEngineIO,
+        player: usize,
+    ) -> Option<LossReason> {
+        rules.level_player(io, player)
+    }
+}
+
+/// The player's deck is empty.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Refresh;
+
+impl RuleAction for Refresh {
+    fn name(&self) -> &'static str {
+        "refresh"
+    }
+
+    fn is_interrupt(&self) -> bool {
+        true
+    }
+
+    fn pending(&self, state: &GameState, player: usize) -> bool {
+        state.players[player].needs_to_refresh()
+    }
+
+    fn perform(
+        &self,
+        rules: &mut Rules,
+        io: &mut dyn RulesEngineIO,
+        player: usize,
+    ) -> Option<LossReason> {
+        rules.refresh_player(io, player)
+    }
+}
+
+/// The player has an unpaid refresh point.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RefreshPoint;
+
+impl RuleAction for RefreshPoint {
+    fn name(&self) -> &'static str {
+        "refresh point"
+    }
+
+    fn is_interrupt(&self) -> bool {
+        false
+    }
+
+    fn pending(&self, state: &GameState, player: usize) -> bool {
+        let player_state = &state.players[player];
+        player_state.refresh_point > 0 && !player_state.deck.content.is_empty()
+    }
+
+    fn perform(
+        &self,
+        rules: &mut Rules,
+        io: &mut dyn RulesEngineIO,
+        player: usize,
+    ) -> Option<LossReason> {
+        let card = rules.state.players[player].pay_refresh_point().unwrap();
+        io.refresh_damage(card, player);
+        None
+    }
+}
+
+/// Every rule action in the game, in the order they are offered to players.
+pub(super) fn default_rule_actions() -> Vec<Rc<dyn RuleAction>> {
+    vec![Rc::new(Level), Rc::new(Refresh), Rc::new(RefreshPoint)]
+}
+
+impl Rules {
+    /// Registers a new rule action, checked after every action
+    /// already registered.
+    pub fn add_rule_action(&mut self, action: Rc<dyn RuleAction>) {
+        self.rule_actions.push(action);
+    }
+
+    /// Collects every automatic ability of the `card` that triggers
+    /// on `trigger` into the `player`'s pending abilities, to be
+    /// resolved at the next check timing.
+    pub(super) fn raise_trigger(&mut self, trigger: Trigger, card: CardId, player: usize) {
+        let abilities: Vec<_> = self
+            .card_data(card)
+            .abilities
+            .iter()
+            .enumerate()
+            .filter(|(_, ability)| {
+                matches!(ability, Ability::Auto { trigger: other, .. } if *other == trigger)
+            })
+            .map(|(index, _)| TriggeredAbility {
+                source: card,
+                ability: index,
+            })
+            .collect();
+
+        self.state.players[player]
+            .pending_abilities
+            .extend(abilities);
+    }
+
+    /// Processes interrupt-type rules.
+    ///
+    /// Each round, starting with the active player, each player performs
+    /// one of their pending interrupt-type rule actions, choosing which
+    /// if they have more than one.  Rounds repeat until neither player
+    /// has any left.
+    ///
+    /// Postconditions: both players have valid game states
+    /// in regards to level-up rules processing (i.e. they
+    /// both have less than 7 cards in clock).
+    ///
+    /// Returns the result of the game if either player lost
+    /// while processing.
+    pub(super) fn interrupt_type_rules_processing<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+    ) -> Result<(), GameResult> {
+        loop {
+            let mut done = true;
+            let mut losses = [None, None];
+
+            for player in [self.state.active_player(), self.state.non_active_player()].iter() {
+                let actions = self.pending_actions(*player, true);
+                if actions.is_empty() {
+                    continue;
+                }
+
+                done = false;
+                let action = Self::choose_action(
+                    io,
+                    &actions,
+                    *player,
+                    ChoiceContext::InterruptTimingChoice,
+                );
+                losses[*player] = self.perform_action(io, action, *player)?;
+            }
+
+            if let Some(result) = GameResult::from_losses(losses) {
+                return Err(result);
+            }
+
+            if done {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Processes a check timing.
+    ///
+    /// 1) Processes interrupt-type rules.
+    ///
+    /// 2) The active player performs one of their pending rule actions or
+    ///    triggered abilities, choosing which if they have more than one.
+    ///    If they have none, the non active player does instead.
+    ///
+    /// 3) Repeats 1) and 2) until neither player has anything pending.
+    ///
+    /// Returns the result of the game if either player lost
+    /// while processing.
+    pub(super) fn check_timing<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
+        loop {
+            self.interrupt_type_rules_processing(io)?;
+
+            let pending = [self.state.active_player(), self.state.non_active_player()]
+                .iter()
+                .map(|player| (*player, self.pending_actions(*player, false)))
+                .find(|(_, actions)| !actions.is_empty());
+
+            let (player, actions) = match pending {
+                Some(pending) => pending,
+                None => return Ok(()),
+            };
+
+            let action =
+                Self::choose_action(io, &actions, player, ChoiceContext::CheckTimingChoice);
+
+            let mut losses = [None, None];
+            losses[player] = self.perform_action(io, action, player)?;
+            if let Some(result) = GameResult::from_losses(losses) {
+                return Err(result);
+            }
+        }
+    }
+
+    /// Returns every action pending for the `player`, either only the
+    /// interrupt-type rule actions, or every other rule action followed
+    /// by their triggered abilities.
+    fn pending_actions(&self, player: usize, interrupt: bool) -> Vec<PendingAction> {
+        let rule_actions = self
+            .rule_actions
+            .iter()
+            .enumerate()
+            .filter(|(_, action)| action.is_interrupt() == interrupt)
+            .filter(|(_, action)| action.pending(&self.state, player))
+            .map(|(index, action)| PendingAction::Rule {
+                index,
+                name: action.name(),
+            });
+
+        let abilities = self.state.players[player]
+            .pending_abilities
+            .iter()
+            .filter(|_| !interrupt)
+            .map(|ability| PendingAction::Triggered(*ability));
+
+        rule_actions.chain(abilities).collect()
+    }
+
+    /// Asks the `player` which of the `actions` to perform first,
+    /// unless there is only one.
+    fn choose_action<T: RulesEngineIO>(
+        io: &mut T,
+        actions: &[PendingAction],
+        player: usize,
+        context: ChoiceContext,
+    ) -> PendingAction {
+        if actions.len() == 1 {
+            actions[0]
+        } else {
+            actions[io.ask_required_choice(actions, player, context)]
+        }
+    }
+
+    /// Performs the pending `action` for the `player`.  A triggered
+    /// ability publishes that it is resolving, then resolves its effect.
+    ///
+    /// Returns the reason the `player` lost, if they did, or the result
+    /// of the game if it ended while resolving an ability.
+    fn perform_action<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        action: PendingAction,
+        player: usize,
+    ) -> Result<Option<LossReason>, GameResult> {
+        match action {
+            PendingAction::Rule { index, .. } => {
+                let action = Rc::clone(&self.rule_actions[index]);
+                Ok(action.perform(self, io, player))
+            }
+            PendingAction::Triggered(ability) => {
+                let pending = &mut self.state.players[player].pending_abilities;
+                let index = pending.iter().position(|item| *item == ability).unwrap();
+                pending.remove(index);
+
+                io.resolve_ability(ability, player);
+                if let Ability::Auto { effect, .. } =
+                    self.card_data(ability.source).abilities[ability.ability]
+                {
+                    self.resolve_effect(io, effect, player)?;
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Refreshes the `player`.
+    ///
+    /// Returns the reason the `player` lost, if their waiting
+    /// room was empty.  Otherwise a refreshed event is emitted.
+    fn refresh_player<T: RulesEngineIO + ?Sized>(
+        &mut self,
+        io: &mut T,
+        player: usize,
+    ) -> Option<LossReason> {
+        let state = &mut self.state;
+        if state.players[player].refresh(&mut state.rng).unwrap() {
+            Some(LossReason::NoCardsToRefresh)
+        } else {
+            io.refreshed(player);
+            None
+        }
+    }
+
+    /// Levels up the `player`.
+    ///
+    /// Postcondition: The `player`'s clock is cleared of
+    /// the bottom 7 cards, and one card is chosen to be put
+    /// in the level zone.  The rest are put in the waiting
+    /// room of that player.  A level up event is emitted.
+    ///
+    /// Returns the reason the `player` lost, if they reached level 4.
+    fn level_player<T: RulesEngineIO + ?Sized>(
+        &mut self,
+        io: &mut T,
+        player: usize,
+    ) -> Option<LossReason> {
+        let player_state = &mut self.state.players[player];
+        let bottom_clock = &player_state.clock.content[0..7];
+        let card_idx = io.ask_required_choice(bottom_clock, player, ChoiceContext::LevelUpProcess);
+        let card = bottom_clock[card_idx];
+
+        let result = player_state.level_up_with(card).unwrap();
+        io.level_up(result, player);
+
+        if player_state.current_level() >= 4 {
+            Some(LossReason::LevelFour)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::card_data::Effect;
+    use crate::rules::io::EventLog;
+
+    /// Creates a card for the `player` whose only ability
+    /// draws a card when it is played, and triggers it.
+    fn ability(rules: &mut Rules, player: usize) -> TriggeredAbility {
+        let card = rules.new_card(player);
+        rules.card_data_mut(card).abilities = vec![Ability::Auto {
+            trigger: Trigger::Play,
+            effect: Effect::Draw(1),
+        }];
+
+        rules.raise_trigger(Trigger::Play, card, player);
+        *rules.state.players[player]
+            .pending_abilities
+            .last()
+            .unwrap()
+    }
+
+    #[test]
+    fn raise_trigger() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let card = rules.active_player().deck.content[0];
+        let data = rules.state.card(card).data;
+
+        rules.data.cards[data.0].abilities = vec![
+            Ability::DoesNotStand,
+            Ability::Auto {
+                trigger: Trigger::Play,
+                effect: Effect::Stock(1),
+            },
+        ];
+
+        rules.raise_trigger(Trigger::Play, card, player);
+
+        assert_eq!(
+            rules.active_player().pending_abilities,
+            vec![TriggeredAbility {
+                source: card,
+                ability: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn pending_actions() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        assert!(rules.pending_actions(player, true).is_empty());
+        assert!(rules.pending_actions(player, false).is_empty());
+
+        rules.active_player_mut().refresh_point = 1;
+        let triggered = ability(&mut rules, player);
+        rules.active_player_mut().clock.content = rules.new_cards(player, 7);
+
+        assert_eq!(
+            rules.pending_actions(player, true),
+            vec![PendingAction::Rule {
+                index: 0,
+                name: "level"
+            }]
+        );
+        assert_eq!(
+            rules.pending_actions(player, false),
+            vec![
+                PendingAction::Rule {
+                    index: 2,
+                    name: "refresh point"
+                },
+                PendingAction::Triggered(triggered)
+            ]
+        );
+    }
+
+    #[test]
+    fn check_timing_resolves_abilities() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let first = ability(&mut rules, player);
+        let second = ability(&mut rules, player);
+        let other = ability(&mut rules, 1 - player);
+
+        let mut io = EventLog::default();
+        rules.check_timing(&mut io).unwrap();
+
+        assert!(rules.state.players[0].pending_abilities.is_empty());
+        assert!(rules.state.players[1].pending_abilities.is_empty());
+        assert_eq!(
+            io.named("resolve_ability"),
+            vec![
+                format!("resolve_ability {:?} {:?}", first, player),
+                format!("resolve_ability {:?} {:?}", second, player),
+                format!("resolve_ability {:?} {:?}", other, 1 - player),
+            ]
+        );
+        assert_eq!(rules.active_player().hand.content.len(), 2);
+        assert_eq!(rules.state.players[1 - player].hand.content.len(), 1);
+    }
+
+    /// A rule action that puts the top card of the stock
+    /// into the waiting room while a player has 2 or more.
+    #[derive(Debug)]
+    struct StockLimit;
+
+    impl RuleAction for StockLimit {
+        fn name(&self) -> &'static str {
+            "stock limit"
+        }
+
+        fn is_interrupt(&self) -> bool {
+            false
+        }
+
+        fn pending(&self, state: &GameState, player: usize) -> bool {
+            state.players[player].stock.content.len() >= 2
+        }
+
+        fn perform(
+            &self,
+            rules: &mut Rules,
+            _: &mut dyn RulesEngineIO,
+            player: usize,
+        ) -> Option<LossReason> {
+            let player_state = &mut rules.state.players[player];
+            let card = player_state.stock.take_top().unwrap();
+            player_state.waiting_room.put_on_top(card);
+            None
+        }
+    }
+
+    #[test]
+    fn add_rule_action() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        rules.add_rule_action(Rc::new(StockLimit));
+
+        for _ in 0..3 {
+            let card = rules.active_player_mut().deck.take_top().unwrap();
+            rules.active_player_mut().stock.put_on_top(card);
+        }
+
+        assert_eq!(
+            rules.pending_actions(player, false),
+            vec![PendingAction::Rule {
+                index: 3,
+                name: "stock limit"
+            }]
+        );
+
+        rules.check_timing(&mut ()).unwrap();
+
+        assert_eq!(rules.active_player().stock.content.len(), 1);
+        assert_eq!(rules.active_player().waiting_room.content.len(), 2);
+    }
+
+    #[test]
+    fn check_timing_interrupts_first() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().refresh_point = 1;
+        rules.active_player_mut().clock.content = rules.new_cards(player, 7);
+
+        rules.check_timing(&mut ()).unwrap();
+
+        assert_eq!(rules.active_player().level.content.len(), 1);
+        assert_eq!(rules.active_player().clock.content.len(), 1);
+        assert_eq!(rules.active_player().refresh_point, 0);
+    }
+}
@@ -0,0 +1,395 @@
+use super::io::{ChoiceContext, RulesEngineIO};
+use super::{GameResult, Rules};
+use crate::data::card_data::CardType;
+use crate::data::cost::Cost;
+use crate::data::CardId;
+use crate::state::memory_state::Facing;
+use crate::state::stage_state::CharacterStatus;
+
+impl Rules {
+    /// Checks to see if the `player` is able to pay every part of the `cost`.
+    ///
+    /// The `source` parameter specifies the card the cost is being paid for.
+    /// It is what "this card" refers to, and can't be used to pay
+    /// for discards from hand.
+    pub fn can_pay_cost(&self, cost: &[Cost], source: CardId, player: usize) -> bool {
+        let player_state = &self.state.players[player];
+
+        let mut stock = 0;
+        let mut discards = 0;
+        let mut character_discards = 0;
+        let mut clocks = 0;
+
+        for part in cost.iter() {
+            match *part {
+                Cost::Stock(amount) => stock += amount,
+                Cost::DiscardFromHand(amount) => discards += amount,
+                Cost::DiscardCharacterFromHand(amount) => {
+                    discards += amount;
+                    character_discards += amount;
+                }
+                Cost::ClockFromDeck => clocks += 1,
+                Cost::RestThis => {
+                    let standing = player_state
+                        .stage
+                        .position_of(source)
+                        .and_then(|position| player_state.stage.get(position))
+                        .is_some_and(|character| character.status == CharacterStatus::Standing);
+                    if !standing {
+                        return false;
+                    }
+                }
+                Cost::ThisToMemory => {
+                    let reachable = player_state.stage.position_of(source).is_some()
+                        || player_state.hand.content.contains(&source)
+                        || player_state.waiting_room.content.contains(&source);
+                    if !reachable {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        let hand = self.discardable_cards(source, player, false);
+        let characters = self.discardable_cards(source, player, true);
+
+        player_state.stock.content.len() >= stock
+            && player_state.deck.content.len() >= clocks
+            && hand.len() >= discards
+            && characters.len() >= character_discards
+    }
+
+    /// Pays every part of the `cost` for the `player`, in order.
+    ///
+    /// Precondition: The `player` is able to pay the `cost`.
+    ///
+    /// Postcondition: A pay cost event is published for each part of the cost,
+    /// along with a discard event for each card discarded, and interrupt-type
+    /// rules are processed.
+    pub(super) fn pay_cost<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        cost: &[Cost],
+        source: CardId,
+        player: usize,
+    ) -> Result<(), GameResult> {
+        for (index, part) in cost.iter().enumerate() {
+            let reserved = cost[index + 1..]
+                .iter()
+                .map(|part| match *part {
+                    Cost::DiscardCharacterFromHand(amount) => amount,
+                    _ => 0,
+                })
+                .sum();
+            let cards = match *part {
+                Cost::Stock(amount) => self.state.players[player].pay_stock(amount).unwrap(),
+                Cost::DiscardFromHand(amount) => (0..amount)
+                    .map(|_| self.discard_for_cost(io, source, player, false, reserved))
+                    .collect(),
+                Cost::DiscardCharacterFromHand(amount) => (0..amount)
+                    .map(|_| self.discard_for_cost(io, source, player, true, reserved))
+                    .collect(),
+                Cost::ClockFromDeck => {
+                    let player_state = &mut self.state.players[player];
+                    let card = player_state.deck.take_top().unwrap();
+                    player_state.clock.put_on_top(card);
+                    vec![card]
+                }
+                Cost::RestThis => {
+                    let stage = &mut self.state.players[player].stage;
+                    let position = stage.position_of(source).unwrap();
+                    stage.rest(position).unwrap();
+                    vec![source]
+                }
+                Cost::ThisToMemory => {
+                    let player_state = &mut self.state.players[player];
+                    let card = match player_state.stage.position_of(source) {
+                        Some(position) => player_state.remove_character(position),
+                        None => player_state
+                            .hand
+                            .take_card_id(source)
+                            .or_else(|| player_state.waiting_room.take_card_id(source)),
+                    };
+                    player_state.memory.put(card.unwrap(), Facing::FaceUp);
+                    vec![source]
+                }
+            };
+            io.pay_cost(*part, cards, player);
+        }
+
+        self.interrupt_type_rules_processing(io)?;
+
+        Ok(())
+    }
+
+    /// Asks the `player` to choose a card from hand to discard
+    /// as part of a cost, and discards it.
+    ///
+    /// The `reserved` parameter is the number of characters later parts
+    /// of the cost still need to discard.  Those characters can't be chosen
+    /// here, so the rest of the cost stays payable.
+    ///
+    /// Returns the card discarded.
+    fn discard_for_cost<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        source: CardId,
+        player: usize,
+        characters_only: bool,
+        reserved: usize,
+    ) -> CardId {
+        let mut options = self.discardable_cards(source, player, characters_only);
+        if self.discardable_cards(source, player, true).len() <= reserved {
+            options.retain(|card| self.card_data(*card).card_type != CardType::Character);
+        }
+        let context = if characters_only {
+            ChoiceContext::PayCostDiscardCharacter
+        } else {
+            ChoiceContext::PayCostDiscard
+        };
+
+        let card = options[io.ask_required_choice(&options, player, context)];
+
+        self.state.players[player].discard_card(card).unwrap();
+        io.discard(card, player);
+        card
+    }
+
+    /// Returns the cards in the `player`'s hand that can be discarded
+    /// to pay a cost for `source`.  The `source` itself is excluded.
+    fn discardable_cards(
+        &self,
+        source: CardId,
+        player: usize,
+        characters_only: bool,
+    ) -> Vec<CardId> {
+        let mut hand = self.state.players[player].hand.content.clone();
+        if let Some(index) = hand.iter().position(|card| *card == source) {
+            hand.remove(index);
+        }
+
+        hand.retain(|card| {
+            !characters_only || self.card_data(*card).card_type == CardType::Character
+        });
+        hand
+    }
+
+    /// Returns the cost to play `card` from hand.
+    pub(super) fn play_cost(&self, card: CardId) -> Vec<Cost> {
+        let cost = self.card_data(card).base_stats.cost;
+        if cost > 0 {
+            vec![Cost::Stock(cost as usize)]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::io::EventLog;
+    use crate::state::memory_state::MemoryCard;
+    use crate::state::stage_state::StagePosition;
+
+    #[test]
+    fn can_pay_stock() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let source = rules.new_card(player);
+
+        assert!(rules.can_pay_cost(&[], source, player));
+        assert!(!rules.can_pay_cost(&[Cost::Stock(1)], source, player));
+
+        rules.active_player_mut().stock.content = rules.new_cards(player, 2);
+
+        assert!(rules.can_pay_cost(&[Cost::Stock(2)], source, player));
+        assert!(!rules.can_pay_cost(&[Cost::Stock(1), Cost::Stock(2)], source, player));
+    }
+
+    #[test]
+    fn pay_stock() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let source = rules.new_card(player);
+        let stock = rules.new_cards(player, 3);
+        rules.active_player_mut().stock.content = stock.clone();
+
+        let mut io = EventLog::default();
+        rules
+            .pay_cost(&mut io, &[Cost::Stock(2)], source, player)
+            .unwrap();
+
+        assert_eq!(
+            io.0,
+            vec![format!(
+                "pay_cost {:?} {:?} {:?}",
+                Cost::Stock(2),
+                vec![stock[2], stock[1]],
+                player
+            )]
+        );
+        assert_eq!(rules.active_player().stock.content, vec![stock[0]]);
+        assert_eq!(
+            rules.active_player().waiting_room.content,
+            vec![stock[2], stock[1]]
+        );
+    }
+
+    #[test]
+    fn discard_from_hand() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let hand = rules.new_cards(player, 3);
+        let source = hand[0];
+        rules.card_data_mut(hand[2]).card_type = CardType::Climax;
+        rules.active_player_mut().hand.content = hand.clone();
+
+        assert!(rules.can_pay_cost(&[Cost::DiscardFromHand(2)], source, player));
+        assert!(!rules.can_pay_cost(&[Cost::DiscardFromHand(3)], source, player));
+        assert!(!rules.can_pay_cost(&[Cost::DiscardCharacterFromHand(2)], source, player));
+
+        let mut io = EventLog::default();
+        rules
+            .pay_cost(
+                &mut io,
+                &[Cost::DiscardCharacterFromHand(1)],
+                source,
+                player,
+            )
+            .unwrap();
+
+        assert_eq!(
+            io.0,
+            vec![
+                format!("discard {:?} {:?}", hand[1], player),
+                format!(
+                    "pay_cost {:?} {:?} {:?}",
+                    Cost::DiscardCharacterFromHand(1),
+                    vec![hand[1]],
+                    player
+                ),
+            ]
+        );
+        assert_eq!(rules.active_player().hand.content, vec![hand[0], hand[2]]);
+        assert_eq!(rules.active_player().waiting_room.content, vec![hand[1]]);
+    }
+
+    #[test]
+    fn discard_keeps_characters_for_later_parts() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let source = rules.new_card(player);
+        let hand = rules.new_cards(player, 2);
+        rules.card_data_mut(hand[1]).card_type = CardType::Climax;
+        rules.active_player_mut().hand.content = hand.clone();
+
+        let cost = [Cost::DiscardFromHand(1), Cost::DiscardCharacterFromHand(1)];
+        assert!(rules.can_pay_cost(&cost, source, player));
+
+        let mut io = EventLog::default();
+        rules.pay_cost(&mut io, &cost, source, player).unwrap();
+
+        assert_eq!(
+            io.named("discard"),
+            vec![
+                format!("discard {:?} {:?}", hand[1], player),
+                format!("discard {:?} {:?}", hand[0], player),
+            ]
+        );
+        assert!(rules.active_player().hand.content.is_empty());
+    }
+
+    #[test]
+    fn rest_this() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let source = rules.new_card(player);
+
+        assert!(!rules.can_pay_cost(&[Cost::RestThis], source, player));
+
+        rules
+            .active_player_mut()
+            .stage
+            .put_in_slot(StagePosition::CenterLeft, source)
+            .unwrap();
+
+        assert!(rules.can_pay_cost(&[Cost::RestThis], source, player));
+
+        let mut io = EventLog::default();
+        rules
+            .pay_cost(&mut io, &[Cost::RestThis], source, player)
+            .unwrap();
+
+        assert_eq!(io.named("pay_cost").len(), 1);
+        assert_eq!(
+            rules
+                .active_player()
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .status,
+            CharacterStatus::Rested
+        );
+        assert!(!rules.can_pay_cost(&[Cost::RestThis], source, player));
+    }
+
+    #[test]
+    fn this_to_memory() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let source = rules.new_card(player);
+
+        assert!(!rules.can_pay_cost(&[Cost::ThisToMemory], source, player));
+
+        rules
+            .active_player_mut()
+            .stage
+            .put_in_slot(StagePosition::CenterLeft, source)
+            .unwrap();
+
+        let mut io = EventLog::default();
+        rules
+            .pay_cost(&mut io, &[Cost::ThisToMemory], source, player)
+            .unwrap();
+
+        assert_eq!(io.named("pay_cost").len(), 1);
+        assert!(rules
+            .active_player()
+            .stage
+            .is_empty(StagePosition::CenterLeft));
+        assert_eq!(
+            rules.active_player().memory.content,
+            vec![MemoryCard {
+                card: source,
+                facing: Facing::FaceUp
+            }]
+        );
+    }
+
+    #[test]
+    fn clock_from_deck() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let source = rules.new_card(player);
+        let top = *rules.active_player().deck.content.last().unwrap();
+
+        assert!(rules.can_pay_cost(&[Cost::ClockFromDeck], source, player));
+
+        let mut io = EventLog::default();
+        rules
+            .pay_cost(&mut io, &[Cost::ClockFromDeck], source, player)
+            .unwrap();
+
+        assert_eq!(rules.active_player().clock.content, vec![top]);
+        assert_eq!(
+            io.0,
+            vec![format!(
+                "pay_cost {:?} {:?} {:?}",
+                Cost::ClockFromDeck,
+                vec![top],
+                player
+            )]
+        );
+    }
+}
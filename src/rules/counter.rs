@@ -0,0 +1,250 @@
+use super::attack::Attack;
+use super::io::{ChoiceContext, RulesEngineIO};
+use super::{GameResult, Rules};
+use crate::data::card_data::{Ability, CardType};
+use crate::data::cost::Cost;
+use crate::data::CardId;
+
+impl Rules {
+    /// Processes the counter step of the `attack`.
+    ///
+    /// 1) Asks the non active player to choose a counter card from their
+    ///    hand to play, or no card.  They are asked even if they have
+    ///    no counter cards they can play.
+    ///
+    /// 2) If a card is chosen, it is put into the resolution zone and its
+    ///    cost is paid, then the defending character gets the card's backup
+    ///    power until end of turn.  The card is put into the waiting room,
+    ///    and a counter event is published.
+    pub(super) fn counter_step<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        attack: &Attack,
+    ) -> Result<(), GameResult> {
+        let player = self.state.non_active_player();
+        let defending = attack
+            .attacker
+            .opposing()
+            .expect("only center stage characters can attack");
+
+        let counters = self.playable_counters(attack, player);
+        let card = io.ask_optional_choice(&counters, player, ChoiceContext::CounterStepCard);
+
+        let card = match card {
+            Some(card) => counters[card],
+            None => return Ok(()),
+        };
+        let (power, cost) = self.counter_ability(card).unwrap();
+
+        let player_state = &mut self.state.players[player];
+        let card = player_state.hand.take_card_id(card).unwrap();
+        player_state.resolution.put_on_top(card);
+
+        self.pay_cost(io, &cost, card, player)?;
+
+        let player_state = &mut self.state.players[player];
+        if let Some(character) = player_state.stage.get_mut(defending) {
+            character.turn_modifiers.power += power;
+        }
+        let card = player_state.resolution.take_card_id(card).unwrap();
+        player_state.waiting_room.put_on_top(card);
+
+        io.counter(card, defending, player);
+
+        Ok(())
+    }
+
+    /// Returns every counter card in the `player`'s hand that they can
+    /// play during the counter step of the `attack`.
+    ///
+    /// If there is no defending character, no counter cards can be played.
+    fn playable_counters(&self, attack: &Attack, player: usize) -> Vec<CardId> {
+        let defending = attack.attacker.opposing();
+        if defending.is_none_or(|defending| self.state.players[player].stage.is_empty(defending)) {
+            return Vec::new();
+        }
+
+        self.state.players[player]
+            .hand
+            .content
+            .iter()
+            .copied()
+            .filter(|card| self.can_play_counter(*card, player))
+            .collect()
+    }
+
+    /// Checks to see if the `player` can play `card` as a counter.
+    ///
+    /// Backup only needs the `player` to be a high enough level, while
+    /// counter events have the same requirements as any other card.
+    fn can_play_counter(&self, card: CardId, player: usize) -> bool {
+        let data = self.card_data(card);
+        let (_, cost) = match self.counter_ability(card) {
+            Some(ability) => ability,
+            None => return false,
+        };
+
+        let meets_requirements = match data.card_type {
+            CardType::Character => {
+                data.base_stats.level <= self.state.players[player].current_level()
+            }
+            CardType::Event => self.meets_play_requirements(card, player),
+            CardType::Climax => false,
+        };
+
+        meets_requirements && self.can_pay_cost(&cost, card, player)
+    }
+
+    /// Returns the backup power and cost of `card` when played as a counter,
+    /// or None if it isn't a counter card.
+    ///
+    /// Counter events also have to pay their cost to play.
+    fn counter_ability(&self, card: CardId) -> Option<(i32, Vec<Cost>)> {
+        let data = self.card_data(card);
+        if !data.counter {
+            return None;
+        }
+
+        let (power, mut cost) = data.abilities.iter().find_map(|ability| match ability {
+            Ability::Backup { power, cost } => Some((*power, cost.clone())),
+            _ => None,
+        })?;
+
+        if data.card_type == CardType::Event {
+            cost.extend(self.play_cost(card));
+        }
+
+        Some((power, cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::io::AttackType;
+    use crate::state::stage_state::StagePosition;
+
+    fn front_attack(rules: &mut Rules) -> Attack {
+        let player = rules.state.active_player;
+        let defender = rules.state.non_active_player();
+        let attacker = rules.new_card(player);
+        let defending = rules.new_card(defender);
+        rules.state.players[player]
+            .stage
+            .put_in_slot(StagePosition::CenterLeft, attacker)
+            .unwrap();
+        rules.state.players[defender]
+            .stage
+            .put_in_slot(StagePosition::CenterRight, defending)
+            .unwrap();
+
+        Attack {
+            attacker: StagePosition::CenterLeft,
+            attack_type: AttackType::Front,
+            shot: false,
+        }
+    }
+
+    fn backup(power: i32, cost: Vec<Cost>) -> Ability {
+        Ability::Backup { power, cost }
+    }
+
+    #[test]
+    fn playable_counters() {
+        let mut rules = Rules::new();
+        let attack = front_attack(&mut rules);
+        let defender = rules.state.non_active_player();
+
+        let cards: Vec<_> = (0..3).map(|_| rules.new_card(defender)).collect();
+        rules.card_data_mut(cards[0]).counter = true;
+        rules.card_data_mut(cards[0]).abilities = vec![backup(1000, vec![])];
+        rules.card_data_mut(cards[1]).counter = true;
+        rules.card_data_mut(cards[1]).abilities = vec![backup(2000, vec![Cost::Stock(1)])];
+        rules.card_data_mut(cards[2]).abilities = vec![backup(3000, vec![])];
+        for card in cards.iter() {
+            rules.state.players[defender].hand.put_on_top(*card);
+        }
+
+        assert_eq!(rules.playable_counters(&attack, defender), vec![cards[0]]);
+
+        let stock = rules.new_card(defender);
+        rules.state.players[defender].stock.put_on_top(stock);
+
+        assert_eq!(
+            rules.playable_counters(&attack, defender),
+            vec![cards[0], cards[1]]
+        );
+
+        rules.state.players[defender]
+            .stage
+            .take_from_slot(StagePosition::CenterRight);
+
+        assert!(rules.playable_counters(&attack, defender).is_empty());
+    }
+
+    #[test]
+    fn counter_event_requirements() {
+        let mut rules = Rules::new();
+        let attack = front_attack(&mut rules);
+        let defender = rules.state.non_active_player();
+
+        let card = rules.new_card(defender);
+        let data = rules.card_data_mut(card);
+        data.card_type = CardType::Event;
+        data.counter = true;
+        data.base_stats.cost = 1;
+        data.abilities = vec![backup(2500, vec![])];
+        rules.state.players[defender].hand.put_on_top(card);
+
+        assert!(rules.playable_counters(&attack, defender).is_empty());
+
+        let stock = rules.new_card(defender);
+        rules.state.players[defender].stock.put_on_top(stock);
+
+        assert_eq!(rules.playable_counters(&attack, defender), vec![card]);
+    }
+
+    #[test]
+    fn counter_step_backup() {
+        let mut rules = Rules::new();
+        let attack = front_attack(&mut rules);
+        let defender = rules.state.non_active_player();
+
+        let card = rules.new_card(defender);
+        rules.card_data_mut(card).counter = true;
+        rules.card_data_mut(card).abilities = vec![backup(1500, vec![Cost::Stock(1)])];
+        let stock = rules.new_card(defender);
+        rules.state.players[defender].hand.put_on_top(card);
+        rules.state.players[defender].stock.put_on_top(stock);
+
+        rules.counter_step(&mut (), &attack).unwrap();
+
+        let defender_state = &rules.state.players[defender];
+        assert!(defender_state.hand.content.is_empty());
+        assert!(defender_state.stock.content.is_empty());
+        assert!(defender_state.resolution.content.is_empty());
+        assert_eq!(defender_state.waiting_room.content, vec![stock, card]);
+        assert_eq!(
+            defender_state
+                .stage
+                .get(StagePosition::CenterRight)
+                .unwrap()
+                .turn_modifiers
+                .power,
+            1500
+        );
+    }
+
+    #[test]
+    fn counter_step_no_counters() {
+        let mut rules = Rules::new();
+        let attack = front_attack(&mut rules);
+        let defender = rules.state.non_active_player();
+        let card = rules.new_card(defender);
+        rules.state.players[defender].hand.put_on_top(card);
+
+        rules.counter_step(&mut (), &attack).unwrap();
+
+        assert_eq!(rules.state.players[defender].hand.content, vec![card]);
+    }
+}
@@ -0,0 +1,169 @@
+use super::io::RulesEngineIO;
+use super::{GameResult, Rules};
+use crate::data::card_data::CardType;
+use crate::state::player_state::DamageResult;
+
+impl Rules {
+    /// Deals `amount` damage to the `player`.  If `amount` isn't positive,
+    /// nothing happens and no events are published.
+    ///
+    /// 1) Reveals the top card of the `player`'s deck into their resolution
+    ///    zone one at a time, publishing a damage revealed event for each.
+    ///
+    /// 2) If a climax is revealed, the damage is cancelled and no more cards
+    ///    are revealed.  Every revealed card is put into the waiting room.
+    ///
+    /// 3) Otherwise every revealed card is put into the clock.
+    ///
+    /// 4) Publishes a damage event, and processes interrupt-type rules.
+    ///
+    /// Returns the result of the damage, or the result of the game if
+    /// a player lost while taking it.
+    pub(super) fn deal_damage<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        player: usize,
+        amount: i32,
+    ) -> Result<DamageResult, GameResult> {
+        let mut result = DamageResult {
+            revealed: Vec::new(),
+            cancelled: false,
+        };
+
+        if amount <= 0 {
+            return Ok(result);
+        }
+
+        for _ in 0..amount {
+            let card = self.state.players[player]
+                .reveal_damage()
+                .expect("can't reveal damage from an empty deck");
+            io.damage_revealed(card, player);
+            result.revealed.push(card);
+
+            self.interrupt_type_rules_processing(io)?;
+
+            if self.card_data(card).card_type == CardType::Climax {
+                result.cancelled = true;
+                break;
+            }
+        }
+
+        let player_state = &mut self.state.players[player];
+        if result.cancelled {
+            player_state.cancel_damage(&result.revealed);
+        } else {
+            player_state.take_damage(&result.revealed);
+        }
+
+        io.damage(result.clone(), player);
+
+        self.interrupt_type_rules_processing(io)?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::io::EventLog;
+
+    #[test]
+    fn deal_damage() {
+        let mut rules = Rules::new();
+        let player = rules.state.non_active_player();
+        let deck = rules.new_cards(player, 4);
+        rules.state.players[player].deck.content = deck.clone();
+
+        let mut io = EventLog::default();
+        let result = rules.deal_damage(&mut io, player, 3).unwrap();
+
+        assert_eq!(
+            result,
+            DamageResult {
+                revealed: vec![deck[3], deck[2], deck[1]],
+                cancelled: false,
+            }
+        );
+        assert_eq!(io.named("damage_revealed").len(), 3);
+        assert_eq!(io.named("damage").len(), 1);
+        assert_eq!(
+            rules.state.players[player].clock.content,
+            vec![deck[3], deck[2], deck[1]]
+        );
+        assert!(rules.state.players[player].resolution.content.is_empty());
+    }
+    #[test]
+    fn deal_no_damage() {
+        let mut rules = Rules::new();
+        let player = rules.state.non_active_player();
+        let deck = rules.state.players[player].deck.content.clone();
+
+        for amount in [0, -1].iter() {
+            let mut io = EventLog::default();
+            let result = rules.deal_damage(&mut io, player, *amount).unwrap();
+
+            assert!(result.revealed.is_empty());
+            assert!(!result.cancelled);
+            assert!(io.0.is_empty());
+        }
+        assert_eq!(rules.state.players[player].deck.content, deck);
+        assert!(rules.state.players[player].clock.content.is_empty());
+    }
+
+    #[test]
+    fn deal_damage_cancelled() {
+        let mut rules = Rules::new();
+        let player = rules.state.non_active_player();
+        let deck = rules.new_cards(player, 4);
+        rules.card_data_mut(deck[2]).card_type = CardType::Climax;
+        rules.state.players[player].deck.content = deck.clone();
+
+        let result = rules.deal_damage(&mut (), player, 3).unwrap();
+
+        assert_eq!(
+            result,
+            DamageResult {
+                revealed: vec![deck[3], deck[2]],
+                cancelled: true,
+            }
+        );
+        assert!(rules.state.players[player].clock.content.is_empty());
+        assert_eq!(
+            rules.state.players[player].waiting_room.content,
+            vec![deck[3], deck[2]]
+        );
+        assert_eq!(
+            rules.state.players[player].deck.content,
+            vec![deck[0], deck[1]]
+        );
+    }
+
+    #[test]
+    fn deal_damage_levels_up() {
+        let mut rules = Rules::new();
+        let player = rules.state.non_active_player();
+        rules.state.players[player].clock.content = rules.new_cards(player, 6);
+
+        rules.deal_damage(&mut (), player, 2).unwrap();
+
+        assert_eq!(rules.state.players[player].level.content.len(), 1);
+        assert_eq!(rules.state.players[player].clock.content.len(), 1);
+    }
+
+    #[test]
+    fn deal_damage_refreshes() {
+        let mut rules = Rules::new();
+        let player = rules.state.non_active_player();
+        rules.state.players[player].deck.content = rules.new_cards(player, 1);
+        rules.state.players[player].waiting_room.content = rules.new_cards(player, 2);
+
+        let result = rules.deal_damage(&mut (), player, 2).unwrap();
+
+        assert_eq!(result.revealed.len(), 2);
+        assert!(!result.cancelled);
+        assert_eq!(rules.state.players[player].refresh_point, 1);
+        assert_eq!(rules.state.players[player].clock.content.len(), 2);
+    }
+}
@@ -0,0 +1,231 @@
+use super::io::{ChoiceContext, RulesEngineIO};
+use super::{GameResult, Rules};
+use crate::data::card_data::Ability;
+use crate::data::cost::Cost;
+use crate::data::CardId;
+use crate::state::stage_state::{CharacterStatus, StagePosition};
+
+/// The encore cost every character has.
+pub const DEFAULT_ENCORE_COST: Cost = Cost::Stock(3);
+
+impl Rules {
+    /// Processes the encore step.
+    ///
+    /// Starting with the active player, each player chooses their reversed
+    /// characters one at a time and puts them into the waiting room.  After
+    /// each one, they may pay an encore cost to return it to the same
+    /// position rested.  A check timing is processed before each choice.
+    pub(super) fn encore_step<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
+        for player in [self.state.active_player(), self.state.non_active_player()].iter() {
+            loop {
+                self.check_timing(io)?;
+
+                let reversed = self.reversed_characters(*player);
+                if reversed.is_empty() {
+                    break;
+                }
+
+                let position =
+                    io.ask_required_choice(&reversed, *player, ChoiceContext::EncoreStepCharacter);
+
+                self.encore_character(io, reversed[position], *player)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns every position of the `player`'s stage with a reversed character.
+    fn reversed_characters(&self, player: usize) -> Vec<StagePosition> {
+        self.state.players[player]
+            .stage
+            .characters()
+            .filter(|(_, character)| character.status == CharacterStatus::Reversed)
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Puts the character in `position` into the waiting room, then asks
+    /// the `player` to choose an encore cost to pay, or no cost.
+    ///
+    /// Postcondition: If a cost was chosen, it is paid and the character is
+    /// put back into `position` rested.  An encore event is published.
+    fn encore_character<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        position: StagePosition,
+        player: usize,
+    ) -> Result<(), GameResult> {
+        let player_state = &mut self.state.players[player];
+        let card = player_state.character_to_waiting_room(position).unwrap();
+
+        let costs = self.payable_encore_costs(card, player);
+        let cost = io
+            .ask_optional_choice(&costs, player, ChoiceContext::EncoreStepCost)
+            .map(|cost| costs[cost].clone());
+
+        if let Some(cost) = &cost {
+            self.pay_cost(io, cost, card, player)?;
+
+            let player_state = &mut self.state.players[player];
+            player_state
+                .stage
+                .put_from_zone(position, &mut player_state.waiting_room, card)
+                .unwrap();
+            player_state.stage.rest(position).unwrap();
+        }
+
+        io.encore(card, position, cost, player);
+
+        self.interrupt_type_rules_processing(io)?;
+
+        Ok(())
+    }
+
+    /// Returns every encore cost for `card` that the `player` is able to pay.
+    ///
+    /// The default encore cost is always first, followed by any encore
+    /// abilities the card has.
+    fn payable_encore_costs(&self, card: CardId, player: usize) -> Vec<Vec<Cost>> {
+        let abilities = self
+            .card_data(card)
+            .abilities
+            .iter()
+            .filter_map(|ability| match ability {
+                Ability::Encore(cost) => Some(cost.clone()),
+                _ => None,
+            });
+
+        std::iter::once(vec![DEFAULT_ENCORE_COST])
+            .chain(abilities)
+            .filter(|cost| self.can_pay_cost(cost, card, player))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates rules where the active player has a reversed character in
+    /// their center left and center right positions, returning both.
+    fn reversed_rules() -> (Rules, [CardId; 2]) {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let left = rules.new_character(player, StagePosition::CenterLeft);
+        let right = rules.new_character(player, StagePosition::CenterRight);
+        let stage = &mut rules.state.players[player].stage;
+        stage.reverse(StagePosition::CenterLeft).unwrap();
+        stage.reverse(StagePosition::CenterRight).unwrap();
+
+        (rules, [left, right])
+    }
+
+    #[test]
+    fn encore_step_without_stock() {
+        let (mut rules, characters) = reversed_rules();
+
+        rules.encore_step(&mut ()).unwrap();
+
+        assert_eq!(rules.active_player().stage.characters().count(), 0);
+        assert_eq!(
+            rules.active_player().waiting_room.content,
+            characters.to_vec()
+        );
+    }
+
+    #[test]
+    fn encore_step_with_stock() {
+        let (mut rules, [left, right]) = reversed_rules();
+        let player = rules.state.active_player;
+        let stock = rules.new_cards(player, 4);
+        rules.active_player_mut().stock.content = stock.clone();
+
+        rules.encore_step(&mut ()).unwrap();
+
+        let stage = &rules.active_player().stage;
+        let character = stage.get(StagePosition::CenterLeft).unwrap();
+        assert_eq!(character.card, left);
+        assert_eq!(character.status, CharacterStatus::Rested);
+        assert!(stage.is_empty(StagePosition::CenterRight));
+
+        assert_eq!(rules.active_player().stock.content, vec![stock[0]]);
+        assert_eq!(
+            rules.active_player().waiting_room.content,
+            vec![stock[3], stock[2], stock[1], right]
+        );
+    }
+
+    #[test]
+    fn payable_encore_costs() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let card = rules.new_card(player);
+        rules.card_data_mut(card).abilities = vec![
+            Ability::Encore(vec![Cost::ClockFromDeck]),
+            Ability::Encore(vec![Cost::DiscardCharacterFromHand(1)]),
+        ];
+
+        assert_eq!(
+            rules.payable_encore_costs(card, player),
+            vec![vec![Cost::ClockFromDeck]]
+        );
+
+        rules.active_player_mut().hand.content = rules.new_cards(player, 1);
+        rules.active_player_mut().stock.content = rules.new_cards(player, 3);
+
+        assert_eq!(
+            rules.payable_encore_costs(card, player),
+            vec![
+                vec![Cost::Stock(3)],
+                vec![Cost::ClockFromDeck],
+                vec![Cost::DiscardCharacterFromHand(1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn clock_encore() {
+        let (mut rules, [left, _]) = reversed_rules();
+        let player = rules.state.active_player;
+        rules.card_data_mut(left).abilities = vec![Ability::Encore(vec![Cost::ClockFromDeck])];
+        let top = *rules.active_player().deck.content.last().unwrap();
+
+        rules
+            .encore_character(&mut (), StagePosition::CenterLeft, player)
+            .unwrap();
+
+        assert_eq!(
+            rules
+                .active_player()
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .status,
+            CharacterStatus::Rested
+        );
+        assert_eq!(rules.active_player().clock.content, vec![top]);
+    }
+
+    #[test]
+    fn hand_encore() {
+        let (mut rules, [left, _]) = reversed_rules();
+        let player = rules.state.active_player;
+        rules.card_data_mut(left).abilities =
+            vec![Ability::Encore(vec![Cost::DiscardCharacterFromHand(1)])];
+        let hand = rules.new_cards(player, 1);
+        rules.active_player_mut().hand.content = hand.clone();
+
+        rules
+            .encore_character(&mut (), StagePosition::CenterLeft, player)
+            .unwrap();
+
+        assert!(rules.active_player().hand.content.is_empty());
+        assert_eq!(rules.active_player().waiting_room.content, hand);
+        assert!(!rules
+            .active_player()
+            .stage
+            .is_empty(StagePosition::CenterLeft));
+    }
+}
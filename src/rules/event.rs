@@ -0,0 +1,214 @@
+use super::io::RulesEngineIO;
+use super::{GameResult, Rules};
+use crate::data::card_data::{Ability, CardType, Effect};
+use crate::data::CardId;
+use crate::state::stage_state::StagePosition;
+
+impl Rules {
+    /// Plays an event for the specified player.
+    ///
+    /// Precondition: `card` must exist in `player`'s hand.
+    ///
+    /// Precondition: The `player` is able to pay the cost of `card`.
+    ///
+    /// 1) Puts `card` into the `player`'s resolution zone, publishes
+    ///    a play event, then pays the cost of `card`.
+    ///
+    /// 2) Resolves each of the event's effects in order.
+    ///
+    /// 3) Puts the event into the waiting room, and publishes that
+    ///    it finished resolving.
+    pub(super) fn play_event<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        card: CardId,
+        player: usize,
+    ) -> Result<(), GameResult> {
+        let player_state = &mut self.state.players[player];
+        let card = player_state.hand.take_card_id(card).unwrap();
+        player_state.resolution.put_on_top(card);
+        io.play_event(card, player);
+
+        let cost = self.play_cost(card);
+        self.pay_cost(io, &cost, card, player)?;
+
+        let effects: Vec<_> = self
+            .card_data(card)
+            .abilities
+            .iter()
+            .filter_map(|ability| match ability {
+                Ability::Event(effect) => Some(*effect),
+                _ => None,
+            })
+            .collect();
+
+        for effect in effects {
+            self.resolve_effect(io, effect, player)?;
+        }
+
+        let player_state = &mut self.state.players[player];
+        let card = player_state.resolution.take_card_id(card).unwrap();
+        player_state.waiting_room.put_on_top(card);
+        io.event_resolved(card, player);
+
+        Ok(())
+    }
+
+    /// Checks to see if `card` is an event the `player` is able to play
+    /// during the main phase, and pay the cost of.
+    ///
+    /// Counter events can only be played during the counter step.
+    pub(super) fn can_play_event(&self, card: CardId, player: usize) -> bool {
+        let data = self.card_data(card);
+
+        data.card_type == CardType::Event
+            && !data.counter
+            && self.meets_play_requirements(card, player)
+            && self.can_pay_cost(&self.play_cost(card), card, player)
+    }
+
+    /// Resolves a single `effect` for the `player`.
+    pub(super) fn resolve_effect<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        effect: Effect,
+        player: usize,
+    ) -> Result<(), GameResult> {
+        match effect {
+            Effect::Draw(amount) => {
+                for _ in 0..amount {
+                    self.draw_card(io, player)?;
+                }
+            }
+            Effect::Stock(amount) => {
+                for _ in 0..amount {
+                    self.interrupt_type_rules_processing(io)?;
+
+                    let player_state = &mut self.state.players[player];
+                    let card = player_state
+                        .deck
+                        .take_top()
+                        .expect("can't put a card into stock from an empty deck");
+                    player_state.stock.put_on_top(card);
+                }
+                self.interrupt_type_rules_processing(io)?;
+            }
+            Effect::Power(amount) => {
+                for position in StagePosition::ALL.iter() {
+                    if let Some(character) = self.state.players[player].stage.get_mut(*position) {
+                        character.turn_modifiers.power += amount;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::card_data::Color;
+    use crate::data::cost::Cost;
+    use crate::rules::io::{EventLog, MainPhaseAction};
+
+    /// Creates a new event owned by the `player` with the `effects`.
+    fn event(rules: &mut Rules, player: usize, effects: Vec<Effect>) -> CardId {
+        let card = rules.new_card(player);
+        let data = rules.card_data_mut(card);
+        data.card_type = CardType::Event;
+        data.abilities = effects.into_iter().map(Ability::Event).collect();
+        card
+    }
+
+    #[test]
+    fn can_play_event() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let card = event(&mut rules, player, vec![Effect::Draw(1)]);
+        let data = rules.card_data_mut(card);
+        data.base_stats.level = 1;
+        data.base_stats.color = Color::Red;
+        data.base_stats.cost = 1;
+        rules.active_player_mut().hand.put_on_top(card);
+
+        assert!(!rules.can_play_event(card, player));
+
+        let level = rules.new_card(player);
+        rules.card_data_mut(level).base_stats.color = Color::Red;
+        rules.active_player_mut().level.put_on_top(level);
+        assert!(!rules.can_play_event(card, player));
+
+        let stock = rules.new_card(player);
+        rules.active_player_mut().stock.put_on_top(stock);
+        assert!(rules.can_play_event(card, player));
+        assert_eq!(
+            rules.main_phase_actions(player),
+            vec![MainPhaseAction::Pass, MainPhaseAction::PlayEvent(card)]
+        );
+
+        rules.card_data_mut(card).counter = true;
+        assert!(!rules.can_play_event(card, player));
+    }
+
+    #[test]
+    fn play_event() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let card = event(
+            &mut rules,
+            player,
+            vec![Effect::Draw(2), Effect::Stock(1), Effect::Power(1000)],
+        );
+        rules.active_player_mut().hand.put_on_top(card);
+        rules.new_character(player, StagePosition::CenterLeft);
+        let starting_deck_size = rules.active_player().deck.content.len();
+
+        rules.play_event(&mut (), card, player).unwrap();
+
+        let player_state = rules.active_player();
+        assert_eq!(player_state.hand.content.len(), 2);
+        assert_eq!(player_state.stock.content.len(), 1);
+        assert_eq!(player_state.deck.content.len(), starting_deck_size - 3);
+        assert!(player_state.resolution.content.is_empty());
+        assert_eq!(player_state.waiting_room.content, vec![card]);
+        assert_eq!(
+            player_state
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .turn_modifiers
+                .power,
+            1000
+        );
+    }
+
+    #[test]
+    fn play_event_pays_from_resolution() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let card = event(&mut rules, player, vec![]);
+        rules.card_data_mut(card).base_stats.cost = 1;
+        rules.active_player_mut().hand.put_on_top(card);
+        let stock = rules.new_card(player);
+        rules.active_player_mut().stock.put_on_top(stock);
+
+        let mut io = EventLog::default();
+        rules.play_event(&mut io, card, player).unwrap();
+
+        assert_eq!(
+            io.0,
+            vec![
+                format!("play_event {:?} {:?}", card, player),
+                format!(
+                    "pay_cost {:?} {:?} {:?}",
+                    Cost::Stock(1),
+                    vec![stock],
+                    player
+                ),
+                format!("event_resolved {:?} {:?}", card, player),
+            ]
+        );
+    }
+}
@@ -0,0 +1,69 @@
+/// The reasons a player can lose the game.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LossReason {
+    /// The player reached level 4.
+    LevelFour,
+    /// The player needed to refresh, but had no cards in their
+    /// deck or waiting room.
+    NoCardsToRefresh,
+}
+
+/// The result of a game that has ended.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameResult {
+    /// One player lost, so the other player won.
+    Win { winner: usize, reason: LossReason },
+    /// Both players lost at the same time.  `reasons`
+    /// is indexed by player.
+    Draw { reasons: [LossReason; 2] },
+}
+
+impl GameResult {
+    /// Creates the result of the game from why each player lost,
+    /// indexed by player.
+    ///
+    /// Returns None if neither player lost.
+    pub fn from_losses(losses: [Option<LossReason>; 2]) -> Option<Self> {
+        match losses {
+            [None, None] => None,
+            [Some(reason), None] => Some(GameResult::Win { winner: 1, reason }),
+            [None, Some(reason)] => Some(GameResult::Win { winner: 0, reason }),
+            [Some(first), Some(second)] => Some(GameResult::Draw {
+                reasons: [first, second],
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_losses() {
+        assert_eq!(GameResult::from_losses([None, None]), None);
+        assert_eq!(
+            GameResult::from_losses([Some(LossReason::LevelFour), None]),
+            Some(GameResult::Win {
+                winner: 1,
+                reason: LossReason::LevelFour
+            })
+        );
+        assert_eq!(
+            GameResult::from_losses([None, Some(LossReason::NoCardsToRefresh)]),
+            Some(GameResult::Win {
+                winner: 0,
+                reason: LossReason::NoCardsToRefresh
+            })
+        );
+        assert_eq!(
+            GameResult::from_losses([
+                Some(LossReason::LevelFour),
+                Some(LossReason::NoCardsToRefresh)
+            ]),
+            Some(GameResult::Draw {
+                reasons: [LossReason::LevelFour, LossReason::NoCardsToRefresh]
+            })
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use super::io::{ChoiceContext, RulesEngineIO, TurnOrder};
+use super::{GameResult, Rules};
+use crate::data::CardId;
+
+/// The number of cards each player draws for their opening hand.
+pub const OPENING_HAND_SIZE: usize = 5;
+
+impl Rules {
+    /// Processes the start of the game.
+    ///
+    /// 1) Shuffles both players' decks.
+    ///
+    /// 2) Decides the first player.  If there is a `chooser`, they choose
+    ///    whether to go first or second, otherwise it is chosen randomly.
+    ///
+    /// 3) Each player draws their opening hand, starting with the first player.
+    ///
+    /// 4) Each player mulligans, starting with the first player.
+    pub fn start_game<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        chooser: Option<usize>,
+    ) -> Result<(), GameResult> {
+        for player in 0..self.state.players.len() {
+            let state = &mut self.state;
+            state.players[player].deck.shuffle(&mut state.rng);
+            io.shuffle(player);
+        }
+
+        let first_player = match chooser {
+            Some(chooser) => {
+                let options = [TurnOrder::First, TurnOrder::Second];
+                let choice =
+                    io.ask_required_choice(&options, chooser, ChoiceContext::GameStartTurnOrder);
+                match options[choice] {
+                    TurnOrder::First => chooser,
+                    TurnOrder::Second => 1 - chooser,
+                }
+            }
+            None => self.state.rng.below(2),
+        };
+
+        self.state.first_player = first_player;
+        self.state.active_player = first_player;
+        self.state.turn = 0;
+        io.first_player(first_player);
+
+        for player in [self.state.active_player(), self.state.non_active_player()].iter() {
+            for _ in 0..OPENING_HAND_SIZE {
+                self.draw_card(io, *player)?;
+            }
+        }
+
+        for player in [self.state.active_player(), self.state.non_active_player()].iter() {
+            self.mulligan(io, *player)?;
+        }
+
+        Ok(())
+    }
+
+    /// Processes the `player`'s mulligan.
+    ///
+    /// The `player` chooses any number of cards from their hand one at a
+    /// time, until they choose no card.  Those cards are put into the
+    /// waiting room, a mulligan event is published, and the `player` draws
+    /// that many cards.
+    fn mulligan<T: RulesEngineIO>(&mut self, io: &mut T, player: usize) -> Result<(), GameResult> {
+        let mut options = self.state.players[player].hand.content.clone();
+        let mut chosen: Vec<CardId> = Vec::new();
+
+        while let Some(choice) =
+            io.ask_optional_choice(&options, player, ChoiceContext::MulliganDiscard)
+        {
+            chosen.push(options.remove(choice));
+        }
+
+        let player_state = &mut self.state.players[player];
+        for card in chosen.iter() {
+            player_state.discard_card(*card).unwrap();
+        }
+        io.mulligan(chosen.clone(), player);
+
+        for _ in 0..chosen.len() {
+            self.draw_card(io, player)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::io::PickX;
+
+    #[test]
+    fn start_game_chosen() {
+        let mut rules = Rules::new();
+
+        rules.start_game(&mut PickX(1), Some(0)).unwrap();
+
+        assert_eq!(rules.state.first_player, 1);
+        assert_eq!(rules.state.active_player, 1);
+        assert_eq!(rules.state.turn, 0);
+        for player in rules.state.players.iter() {
+            assert_eq!(player.hand.content.len(), OPENING_HAND_SIZE);
+            assert_eq!(
+                player.deck.content.len() + player.waiting_room.content.len(),
+                50 - OPENING_HAND_SIZE
+            );
+        }
+    }
+
+    #[test]
+    fn start_game_random() {
+        let mut rules = Rules::new();
+
+        rules.start_game(&mut PickX(0), None).unwrap();
+
+        assert!(rules.state.first_player < 2);
+        assert_eq!(rules.state.active_player, rules.state.first_player);
+    }
+
+    #[test]
+    fn start_game_seeded() {
+        let mut first = Rules::with_seed(5);
+        let mut second = Rules::with_seed(5);
+
+        first.start_game(&mut PickX(0), None).unwrap();
+        second.start_game(&mut PickX(0), None).unwrap();
+
+        assert_eq!(first.state.first_player, second.state.first_player);
+        for (first, second) in first.state.players.iter().zip(second.state.players.iter()) {
+            assert_eq!(first.hand.content, second.hand.content);
+            assert_eq!(first.deck.content, second.deck.content);
+        }
+    }
+
+    #[test]
+    fn mulligan() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        for _ in 0..OPENING_HAND_SIZE {
+            rules.active_player_mut().draw_card().unwrap();
+        }
+
+        rules.mulligan(&mut (), player).unwrap();
+
+        assert_eq!(rules.active_player().hand.content.len(), OPENING_HAND_SIZE);
+        assert_eq!(
+            rules.active_player().waiting_room.content.len(),
+            OPENING_HAND_SIZE
+        );
+        assert_eq!(
+            rules.active_player().deck.content.len(),
+            50 - 2 * OPENING_HAND_SIZE
+        );
+    }
+
+    #[test]
+    fn mulligan_nothing() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let card = rules.new_card(player);
+        rules.active_player_mut().hand.put_on_top(card);
+
+        rules.mulligan(&mut PickX(1), player).unwrap();
+
+        assert_eq!(rules.active_player().hand.content, vec![card]);
+        assert!(rules.active_player().waiting_room.content.is_empty());
+    }
+}
@@ -1,5 +1,8 @@
+use crate::data::card_data::TriggerIcon;
+use crate::data::cost::Cost;
 use crate::data::{CardId, Phase};
-use crate::state::player_state::LevelUpResult;
+use crate::state::player_state::{DamageResult, LevelUpResult, TriggeredAbility};
+use crate::state::stage_state::StagePosition;
 
 /// The context for a given request for user input.  You should render a message based on the value of this enum.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -8,12 +11,77 @@ pub enum ChoiceContext {
     HandLimitDiscard,
     LevelUpProcess,
     InterruptTimingChoice,
+    MainPhaseAction,
+    MainPhaseCharacterPosition,
+    ClimaxPhaseCardToPlay,
+    AttackDeclarationAttacker,
+    AttackDeclarationAttackType,
+    TriggerStepUseEffect,
+    TriggerStepBounceTarget,
+    TriggerStepGateTarget,
+    TriggerStepDoorTarget,
+    TriggerStepChoiceTarget,
+    TriggerStepChoiceDestination,
+    TriggerStepStandbyTarget,
+    TriggerStepStandbyPosition,
+    EncoreStepCharacter,
+    EncoreStepCost,
+    PayCostDiscard,
+    PayCostDiscardCharacter,
+    CheckTimingChoice,
+    GameStartTurnOrder,
+    MulliganDiscard,
+    CounterStepCard,
+    MainPhaseMoveDestination,
 }
 
+/// The actions that can be waiting to be performed at a check timing.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum InterruptChoice {
-    Refresh,
-    Level,
+pub enum PendingAction {
+    /// A rule action whose condition is met, with its
+    /// index among the rules' registered rule actions.
+    Rule { index: usize, name: &'static str },
+    /// A triggered ability waiting to resolve.
+    Triggered(TriggeredAbility),
+}
+
+/// The actions the active player can take during the main phase.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MainPhaseAction {
+    /// Ends the main phase.
+    Pass,
+    /// Plays the character from hand onto the stage.
+    PlayCharacter(CardId),
+    /// Plays the event from hand.
+    PlayEvent(CardId),
+    /// Moves the character in the position to another position,
+    /// swapping with any character already there.
+    MoveCharacter(StagePosition),
+}
+
+/// The kinds of attacks a character can make.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AttackType {
+    /// Attacks the character in front of the attacker, and will battle it.
+    Front,
+    /// Attacks the character in front of the attacker, without battling it.
+    Side,
+    /// Attacks when there is no character in front of the attacker.
+    Direct,
+}
+
+/// Whether a player chooses to go first or second.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TurnOrder {
+    First,
+    Second,
+}
+
+/// Where a card chosen by a choice trigger can be put.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ChoiceTriggerDestination {
+    Hand,
+    Stock,
 }
 
 /// A trait describing the way the rules engine will request and provide information to the client.
@@ -57,10 +125,177 @@ pub trait Output {
     /// The `player` parameter specifies who leveled up (not the active player).
     fn clock(&mut self, card: CardId, player: usize);
 
+    /// This is called whenever a player's deck is shuffled at the start of the game.
+    ///
+    /// The `player` parameter specifies whose deck was shuffled.
+    fn shuffle(&mut self, player: usize);
+
+    /// This is called once the first player of the game has been decided.
+    ///
+    /// The `player` parameter specifies who goes first.
+    fn first_player(&mut self, player: usize);
+
+    /// This is called whenever a player finishes choosing cards to mulligan.
+    ///
+    /// The `cards` parameter specifies which cards were put into the waiting room.
+    ///
+    /// The `player` parameter specifies who mulliganed.
+    fn mulligan(&mut self, cards: Vec<CardId>, player: usize);
+
     /// This is called whenever a player refreshes.
     ///
     /// The `player` parameter specifies who leveled up (not the active player).
     fn refreshed(&mut self, player: usize);
+
+    /// This is called whenever a player takes refresh damage for a refresh point.
+    ///
+    /// The `card` parameter specifies which card was put into the clock.
+    ///
+    /// The `player` parameter specifies who took the damage (not the active player).
+    fn refresh_damage(&mut self, card: CardId, player: usize);
+
+    /// This is called whenever a triggered ability resolves at a check timing.
+    ///
+    /// The `ability` parameter specifies which ability resolved.
+    ///
+    /// The `player` parameter specifies whose ability it is (not the active player).
+    fn resolve_ability(&mut self, ability: TriggeredAbility, player: usize);
+
+    /// This is called whenever a player plays a character onto the stage.
+    ///
+    /// The `card` parameter specifies which character was played.
+    ///
+    /// The `position` parameter specifies where on the stage it was played.
+    ///
+    /// The `player` parameter specifies who played the character (not the active player).
+    fn play_character(&mut self, card: CardId, position: StagePosition, player: usize);
+
+    /// This is called whenever a player moves a character on the stage.
+    ///
+    /// The `from` parameter specifies where the character was.
+    ///
+    /// The `to` parameter specifies where the character was moved to.  If there
+    /// was a character there, it was swapped into `from`.
+    ///
+    /// The `player` parameter specifies whose character it is (not the active player).
+    fn move_character(&mut self, from: StagePosition, to: StagePosition, player: usize);
+
+    /// This is called whenever a player plays an event, before it resolves.
+    ///
+    /// The `card` parameter specifies which event was played.
+    ///
+    /// The `player` parameter specifies who played the event (not the active player).
+    fn play_event(&mut self, card: CardId, player: usize);
+
+    /// This is called whenever an event finishes resolving and is put into the waiting room.
+    ///
+    /// The `card` parameter specifies which event resolved.
+    ///
+    /// The `player` parameter specifies who played the event (not the active player).
+    fn event_resolved(&mut self, card: CardId, player: usize);
+
+    /// This is called whenever a player plays a climax during the Climax phase.
+    ///
+    /// The `card` parameter specifies which card was put into the climax zone.
+    ///
+    /// The `player` parameter specifies who played the climax (not the active player).
+    fn play_climax(&mut self, card: CardId, player: usize);
+
+    /// This is called whenever a player declares an attack.
+    ///
+    /// The `attacker` parameter specifies the stage position of the attacking character.
+    ///
+    /// The `attack_type` parameter specifies what kind of attack was declared.
+    ///
+    /// The `player` parameter specifies who declared the attack.
+    fn declare_attack(&mut self, attacker: StagePosition, attack_type: AttackType, player: usize);
+
+    /// This is called whenever a card is revealed during the trigger step.
+    ///
+    /// The `card` parameter specifies which card was revealed.
+    ///
+    /// The `player` parameter specifies whose deck the card was revealed from.
+    fn trigger(&mut self, card: CardId, player: usize);
+
+    /// This is called whenever the effect of a trigger icon is resolved.
+    ///
+    /// The `icon` parameter specifies which trigger icon was resolved.
+    ///
+    /// The `card` parameter specifies the card the effect moved, if the
+    /// effect moved a card that is not hidden.  Drawn cards will be
+    /// available in separate draw events.
+    ///
+    /// The `player` parameter specifies who resolved the trigger.
+    fn trigger_effect(&mut self, icon: TriggerIcon, card: Option<CardId>, player: usize);
+
+    /// This is called whenever a card is revealed while a player takes damage.
+    ///
+    /// The `card` parameter specifies which card was revealed.
+    ///
+    /// The `player` parameter specifies who is taking the damage.
+    fn damage_revealed(&mut self, card: CardId, player: usize);
+
+    /// This is called whenever a player has finished taking damage.
+    ///
+    /// The `result` parameter specifies every card that was revealed,
+    /// and whether the damage was cancelled.  Revealed cards go to the
+    /// clock, or the waiting room if the damage was cancelled.
+    ///
+    /// The `player` parameter specifies who took the damage.
+    fn damage(&mut self, result: DamageResult, player: usize);
+
+    /// This is called whenever a character on the stage is stood during the stand phase.
+    ///
+    /// The `position` parameter specifies where the stood character is on the stage.
+    ///
+    /// The `player` parameter specifies whose character was stood.
+    fn stand(&mut self, position: StagePosition, player: usize);
+
+    /// This is called whenever a player plays a counter card during the counter step.
+    ///
+    /// The `card` parameter specifies which card was played.
+    ///
+    /// The `position` parameter specifies where the defending character is on the stage.
+    ///
+    /// The `player` parameter specifies who played the counter (not the active player).
+    fn counter(&mut self, card: CardId, position: StagePosition, player: usize);
+
+    /// This is called whenever a character on the stage is reversed.
+    ///
+    /// The `position` parameter specifies where the reversed character is on the stage.
+    ///
+    /// The `player` parameter specifies whose character was reversed.
+    fn reverse(&mut self, position: StagePosition, player: usize);
+
+    /// This is called whenever a player pays part of a cost.
+    ///
+    /// The `cost` parameter specifies which part of the cost was paid.
+    ///
+    /// The `cards` parameter specifies the cards that were moved or rested to pay it.
+    /// Discarded cards also get separate discard events.
+    ///
+    /// The `player` parameter specifies who paid the cost.
+    fn pay_cost(&mut self, cost: Cost, cards: Vec<CardId>, player: usize);
+
+    /// This is called whenever a reversed character is put into the
+    /// waiting room during the encore step.
+    ///
+    /// The `card` parameter specifies which character was put into the waiting room.
+    ///
+    /// The `position` parameter specifies where on the stage the character was.
+    ///
+    /// The `cost` parameter specifies the encore cost that was paid to return
+    /// the character to the same position rested, or None if it stayed
+    /// in the waiting room.
+    ///
+    /// The `player` parameter specifies whose character it is.
+    fn encore(
+        &mut self,
+        card: CardId,
+        position: StagePosition,
+        cost: Option<Vec<Cost>>,
+        player: usize,
+    );
 }
 
 pub trait Input<T> {
@@ -106,7 +341,19 @@ pub trait Input<T> {
     ) -> usize;
 }
 
-pub trait RulesEngineIO: Output + Input<CardId> + Input<InterruptChoice> {}
+pub trait RulesEngineIO:
+    Output
+    + Input<CardId>
+    + Input<PendingAction>
+    + Input<TurnOrder>
+    + Input<MainPhaseAction>
+    + Input<StagePosition>
+    + Input<AttackType>
+    + Input<TriggerIcon>
+    + Input<ChoiceTriggerDestination>
+    + Input<Vec<Cost>>
+{
+}
 
 impl Output for () {
     fn phase_change(&mut self, _: Phase, _: usize) {}
@@ -114,7 +361,27 @@ impl Output for () {
     fn discard(&mut self, _: CardId, _: usize) {}
     fn level_up(&mut self, _: LevelUpResult, _: usize) {}
     fn clock(&mut self, _: CardId, _: usize) {}
+    fn shuffle(&mut self, _: usize) {}
+    fn first_player(&mut self, _: usize) {}
+    fn mulligan(&mut self, _: Vec<CardId>, _: usize) {}
     fn refreshed(&mut self, _: usize) {}
+    fn refresh_damage(&mut self, _: CardId, _: usize) {}
+    fn resolve_ability(&mut self, _: TriggeredAbility, _: usize) {}
+    fn play_character(&mut self, _: CardId, _: StagePosition, _: usize) {}
+    fn move_character(&mut self, _: StagePosition, _: StagePosition, _: usize) {}
+    fn play_event(&mut self, _: CardId, _: usize) {}
+    fn event_resolved(&mut self, _: CardId, _: usize) {}
+    fn play_climax(&mut self, _: CardId, _: usize) {}
+    fn declare_attack(&mut self, _: StagePosition, _: AttackType, _: usize) {}
+    fn trigger(&mut self, _: CardId, _: usize) {}
+    fn trigger_effect(&mut self, _: TriggerIcon, _: Option<CardId>, _: usize) {}
+    fn damage_revealed(&mut self, _: CardId, _: usize) {}
+    fn damage(&mut self, _: DamageResult, _: usize) {}
+    fn stand(&mut self, _: StagePosition, _: usize) {}
+    fn counter(&mut self, _: CardId, _: StagePosition, _: usize) {}
+    fn reverse(&mut self, _: StagePosition, _: usize) {}
+    fn pay_cost(&mut self, _: Cost, _: Vec<CardId>, _: usize) {}
+    fn encore(&mut self, _: CardId, _: StagePosition, _: Option<Vec<Cost>>, _: usize) {}
 }
 
 impl<T> Input<T> for () {
@@ -141,15 +408,35 @@ impl Output for PickX {
     fn discard(&mut self, _: CardId, _: usize) {}
     fn level_up(&mut self, _: LevelUpResult, _: usize) {}
     fn clock(&mut self, _: CardId, _: usize) {}
+    fn shuffle(&mut self, _: usize) {}
+    fn first_player(&mut self, _: usize) {}
+    fn mulligan(&mut self, _: Vec<CardId>, _: usize) {}
     fn refreshed(&mut self, _: usize) {}
+    fn refresh_damage(&mut self, _: CardId, _: usize) {}
+    fn resolve_ability(&mut self, _: TriggeredAbility, _: usize) {}
+    fn play_character(&mut self, _: CardId, _: StagePosition, _: usize) {}
+    fn move_character(&mut self, _: StagePosition, _: StagePosition, _: usize) {}
+    fn play_event(&mut self, _: CardId, _: usize) {}
+    fn event_resolved(&mut self, _: CardId, _: usize) {}
+    fn play_climax(&mut self, _: CardId, _: usize) {}
+    fn declare_attack(&mut self, _: StagePosition, _: AttackType, _: usize) {}
+    fn trigger(&mut self, _: CardId, _: usize) {}
+    fn trigger_effect(&mut self, _: TriggerIcon, _: Option<CardId>, _: usize) {}
+    fn damage_revealed(&mut self, _: CardId, _: usize) {}
+    fn damage(&mut self, _: DamageResult, _: usize) {}
+    fn stand(&mut self, _: StagePosition, _: usize) {}
+    fn counter(&mut self, _: CardId, _: StagePosition, _: usize) {}
+    fn reverse(&mut self, _: StagePosition, _: usize) {}
+    fn pay_cost(&mut self, _: Cost, _: Vec<CardId>, _: usize) {}
+    fn encore(&mut self, _: CardId, _: StagePosition, _: Option<Vec<Cost>>, _: usize) {}
 }
 
 impl<T> Input<T> for PickX {
     fn ask_optional_choice(&mut self, options: &[T], _: usize, _: ChoiceContext) -> Option<usize> {
-        if options.is_empty() {
-            None
-        } else {
+        if self.0 < options.len() {
             Some(self.0)
+        } else {
+            None
         }
     }
 
@@ -159,3 +446,151 @@ impl<T> Input<T> for PickX {
 }
 
 impl RulesEngineIO for PickX {}
+
+/// An IO that makes the same choices as `()`, and records every event
+/// published as its method name followed by its parameters, for tests.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct EventLog(pub Vec<String>);
+
+#[cfg(test)]
+impl EventLog {
+    /// Returns every recorded event with the `name`.
+    pub fn named(&self, name: &str) -> Vec<&str> {
+        self.0
+            .iter()
+            .filter(|event| event.split(' ').next() == Some(name))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl Output for EventLog {
+    fn phase_change(&mut self, phase: Phase, player: usize) {
+        self.0
+            .push(format!("phase_change {:?} {:?}", phase, player));
+    }
+    fn draw(&mut self, card: CardId, player: usize) {
+        self.0.push(format!("draw {:?} {:?}", card, player));
+    }
+    fn discard(&mut self, card: CardId, player: usize) {
+        self.0.push(format!("discard {:?} {:?}", card, player));
+    }
+    fn level_up(&mut self, result: LevelUpResult, player: usize) {
+        self.0.push(format!("level_up {:?} {:?}", result, player));
+    }
+    fn clock(&mut self, card: CardId, player: usize) {
+        self.0.push(format!("clock {:?} {:?}", card, player));
+    }
+    fn shuffle(&mut self, player: usize) {
+        self.0.push(format!("shuffle {:?}", player));
+    }
+    fn first_player(&mut self, player: usize) {
+        self.0.push(format!("first_player {:?}", player));
+    }
+    fn mulligan(&mut self, cards: Vec<CardId>, player: usize) {
+        self.0.push(format!("mulligan {:?} {:?}", cards, player));
+    }
+    fn refreshed(&mut self, player: usize) {
+        self.0.push(format!("refreshed {:?}", player));
+    }
+    fn refresh_damage(&mut self, card: CardId, player: usize) {
+        self.0
+            .push(format!("refresh_damage {:?} {:?}", card, player));
+    }
+    fn resolve_ability(&mut self, ability: TriggeredAbility, player: usize) {
+        self.0
+            .push(format!("resolve_ability {:?} {:?}", ability, player));
+    }
+    fn play_character(&mut self, card: CardId, position: StagePosition, player: usize) {
+        self.0.push(format!(
+            "play_character {:?} {:?} {:?}",
+            card, position, player
+        ));
+    }
+    fn move_character(&mut self, from: StagePosition, to: StagePosition, player: usize) {
+        self.0
+            .push(format!("move_character {:?} {:?} {:?}", from, to, player));
+    }
+    fn play_event(&mut self, card: CardId, player: usize) {
+        self.0.push(format!("play_event {:?} {:?}", card, player));
+    }
+    fn event_resolved(&mut self, card: CardId, player: usize) {
+        self.0
+            .push(format!("event_resolved {:?} {:?}", card, player));
+    }
+    fn play_climax(&mut self, card: CardId, player: usize) {
+        self.0.push(format!("play_climax {:?} {:?}", card, player));
+    }
+    fn declare_attack(&mut self, attacker: StagePosition, attack_type: AttackType, player: usize) {
+        self.0.push(format!(
+            "declare_attack {:?} {:?} {:?}",
+            attacker, attack_type, player
+        ));
+    }
+    fn trigger(&mut self, card: CardId, player: usize) {
+        self.0.push(format!("trigger {:?} {:?}", card, player));
+    }
+    fn trigger_effect(&mut self, icon: TriggerIcon, card: Option<CardId>, player: usize) {
+        self.0
+            .push(format!("trigger_effect {:?} {:?} {:?}", icon, card, player));
+    }
+    fn damage_revealed(&mut self, card: CardId, player: usize) {
+        self.0
+            .push(format!("damage_revealed {:?} {:?}", card, player));
+    }
+    fn damage(&mut self, result: DamageResult, player: usize) {
+        self.0.push(format!("damage {:?} {:?}", result, player));
+    }
+    fn stand(&mut self, position: StagePosition, player: usize) {
+        self.0.push(format!("stand {:?} {:?}", position, player));
+    }
+    fn counter(&mut self, card: CardId, position: StagePosition, player: usize) {
+        self.0
+            .push(format!("counter {:?} {:?} {:?}", card, position, player));
+    }
+    fn reverse(&mut self, position: StagePosition, player: usize) {
+        self.0.push(format!("reverse {:?} {:?}", position, player));
+    }
+    fn pay_cost(&mut self, cost: Cost, cards: Vec<CardId>, player: usize) {
+        self.0
+            .push(format!("pay_cost {:?} {:?} {:?}", cost, cards, player));
+    }
+    fn encore(
+        &mut self,
+        card: CardId,
+        position: StagePosition,
+        cost: Option<Vec<Cost>>,
+        player: usize,
+    ) {
+        self.0.push(format!(
+            "encore {:?} {:?} {:?} {:?}",
+            card, position, cost, player
+        ));
+    }
+}
+
+#[cfg(test)]
+impl<T> Input<T> for EventLog {
+    fn ask_optional_choice(
+        &mut self,
+        options: &[T],
+        player: usize,
+        context: ChoiceContext,
+    ) -> Option<usize> {
+        ().ask_optional_choice(options, player, context)
+    }
+
+    fn ask_required_choice(
+        &mut self,
+        options: &[T],
+        player: usize,
+        context: ChoiceContext,
+    ) -> usize {
+        ().ask_required_choice(options, player, context)
+    }
+}
+
+#[cfg(test)]
+impl RulesEngineIO for EventLog {}
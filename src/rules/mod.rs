@@ -1,10 +1,27 @@
+mod attack;
+mod check_timing;
+mod cost;
+mod counter;
+mod damage;
+mod encore;
+mod event;
+mod game_result;
+mod game_start;
 pub mod io;
+mod trigger;
 
+pub use check_timing::{Level, Refresh, RefreshPoint, RuleAction};
+pub use game_result::{GameResult, LossReason};
+
+use crate::data::card_data::{Ability, CardData, CardType, Trigger};
+use crate::data::deck_list::DeckList;
 use crate::data::game_data::GameData;
 use crate::data::{CardId, Phase};
 use crate::state::game_state::GameState;
 use crate::state::player_state::PlayerState;
-use io::{ChoiceContext, InterruptChoice, RulesEngineIO};
+use crate::state::stage_state::{CharacterStatus, StagePosition};
+use io::{ChoiceContext, MainPhaseAction, RulesEngineIO};
+use std::rc::Rc;
 
 /// The engine that will hold all the game state and data
 /// to run rules checking and processing.  All functions
@@ -15,15 +32,66 @@ use io::{ChoiceContext, InterruptChoice, RulesEngineIO};
 pub struct Rules {
     state: GameState,
     data: GameData,
+    /// The rule actions checked at every check timing, in the
+    /// order they are offered to players.
+    rule_actions: Vec<Rc<dyn RuleAction>>,
 }
 
 impl Rules {
-    /// Creates a default engine.  Will be deprecated once a more
-    /// robust game creation method exists.
+    /// Creates a default engine, where every random choice is decided
+    /// by the [`DEFAULT_SEED`](crate::state::game_state::DEFAULT_SEED).
+    /// Will be deprecated once a more robust game creation method exists.
     pub fn new() -> Self {
         Rules {
             state: GameState::new(),
-            data: GameData {},
+            data: GameData::with_default_cards(50),
+            rule_actions: check_timing::default_rule_actions(),
+        }
+    }
+
+    /// Creates an engine for a game between the `decks`, made of
+    /// the cards in `data`, where every random choice is decided
+    /// by the `seed`.
+    ///
+    /// Precondition: every card in the `decks` must exist in the `data`.
+    pub fn with_decks(data: GameData, decks: [&DeckList; 2], seed: u64) -> Self {
+        Rules {
+            state: GameState::with_decks(decks, seed),
+            data,
+            rule_actions: check_timing::default_rule_actions(),
+        }
+    }
+
+    /// Creates a default engine, where every random choice is
+    /// decided by the `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Rules {
+            state: GameState::with_seed(seed),
+            data: GameData::with_default_cards(50),
+            rule_actions: check_timing::default_rule_actions(),
+        }
+    }
+
+    /// Returns the static data of the `card`, from the
+    /// definition it was created from.
+    pub fn card_data(&self, card: CardId) -> &CardData {
+        self.data.card(self.state.card(card).data)
+    }
+
+    /// Starts the game, then runs through it one turn at a time,
+    /// until a player loses.
+    ///
+    /// If there is a `chooser`, they choose whether to go first
+    /// or second, otherwise the first player is chosen randomly.
+    pub fn run_game<T: RulesEngineIO>(&mut self, io: &mut T, chooser: Option<usize>) -> GameResult {
+        if let Err(result) = self.start_game(io, chooser) {
+            return result;
+        }
+
+        loop {
+            if let Some(result) = self.run_turn(io) {
+                return result;
+            }
         }
     }
 
@@ -31,14 +99,29 @@ impl Rules {
     /// the turn is over, the turn will be switched to the next player.
     /// Generally this should be called in a loop until the game is
     /// over.
-    pub fn run_turn<T: RulesEngineIO>(&mut self, io: &mut T) {
+    ///
+    /// Returns the result of the game if it ended during the turn, in
+    /// which case the turn stops immediately.
+    pub fn run_turn<T: RulesEngineIO>(&mut self, io: &mut T) -> Option<GameResult> {
+        self.turn_phases(io).err()
+    }
+
+    fn turn_phases<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
         self.stand_phase(io);
 
-        self.draw_phase(io);
+        self.draw_phase(io)?;
+
+        self.clock_phase(io)?;
+
+        self.main_phase(io)?;
 
-        self.clock_phase(io);
+        self.climax_phase(io);
+
+        self.attack_phase(io)?;
 
         self.end_phase(io);
+
+        Ok(())
     }
 
     /// Returns a reference to the active player.
@@ -47,16 +130,40 @@ impl Rules {
     }
 
     /// Returns a mutable reference to the active player.
-    #[allow(dead_code)]
     fn active_player_mut(&mut self) -> &mut PlayerState {
         &mut self.state.players[self.state.active_player]
     }
 
     /// Processes the stand phase.
     ///
-    /// Publishes a Phase change with the value `Phase::Stand`
+    /// 1) Publishes a Phase change with the value `Phase::Stand`
+    ///
+    /// 2) Stands every rested and reversed character of the active player,
+    ///    unless it does not stand during the stand phase.  A stand event
+    ///    is published for each character stood, and its abilities that
+    ///    trigger on standing are pending until the next check timing.
     fn stand_phase<T: RulesEngineIO>(&mut self, io: &mut T) {
         self.phase_change(io, Phase::Stand);
+
+        let player = self.state.active_player;
+        let to_stand: Vec<_> = self.state.players[player]
+            .stage
+            .characters()
+            .filter(|(_, character)| character.status != CharacterStatus::Standing)
+            .filter(|(_, character)| {
+                !self
+                    .card_data(character.card)
+                    .abilities
+                    .contains(&Ability::DoesNotStand)
+            })
+            .map(|(position, character)| (position, character.card))
+            .collect();
+
+        for (position, card) in to_stand {
+            self.state.players[player].stage.stand(position).unwrap();
+            io.stand(position, player);
+            self.raise_trigger(Trigger::Stand, card, player);
+        }
     }
 
     /// Processes the draw phase.
@@ -64,9 +171,13 @@ impl Rules {
     /// 1) Publishes a Phase change with the value `Phase::Draw`
     ///
     /// 2) Draws a card for the active player.
-    fn draw_phase<T: RulesEngineIO>(&mut self, io: &mut T) {
+    ///
+    /// 3) Processes a check timing.
+    fn draw_phase<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
         self.phase_change(io, Phase::Draw);
-        self.draw_card(io, self.state.active_player);
+        self.draw_card(io, self.state.active_player)?;
+
+        self.check_timing(io)
     }
 
     /// Processes the clock phase.
@@ -76,7 +187,9 @@ impl Rules {
     /// 2) Asks the active player to choose a card to clock, or no card.
     ///
     /// 3) If a card is chosen, perform the clock draw two action.
-    fn clock_phase<T: RulesEngineIO>(&mut self, io: &mut T) {
+    ///
+    /// 4) Processes a check timing.
+    fn clock_phase<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
         self.phase_change(io, Phase::Clock);
         let card = io.ask_optional_choice(
             &self.active_player().hand.content,
@@ -86,22 +199,100 @@ impl Rules {
 
         if let Some(card) = card {
             let card = self.active_player().hand.content[card];
-            self.clock_card(io, card, self.state.active_player);
+            self.clock_card(io, card, self.state.active_player)?;
         }
+
+        self.check_timing(io)
     }
 
-    /// Processes the clock phase.
+    /// Processes the main phase.
+    ///
+    /// 1) Publishes a Phase change with the value `Phase::Main`
+    ///
+    /// 2) Processes a check timing, then asks the active player to
+    ///    choose an action, and performs it.
+    ///
+    /// 3) Repeats 2) until the active player chooses to pass.
+    fn main_phase<T: RulesEngineIO>(&mut self, io: &mut T) -> Result<(), GameResult> {
+        self.phase_change(io, Phase::Main);
+
+        let player = self.state.active_player;
+        loop {
+            self.check_timing(io)?;
+
+            let actions = self.main_phase_actions(player);
+            let action = io.ask_required_choice(&actions, player, ChoiceContext::MainPhaseAction);
+
+            match actions[action] {
+                MainPhaseAction::Pass => break,
+                MainPhaseAction::PlayCharacter(card) => {
+                    let position = io.ask_required_choice(
+                        &StagePosition::ALL,
+                        player,
+                        ChoiceContext::MainPhaseCharacterPosition,
+                    );
+                    self.play_character(io, card, StagePosition::ALL[position], player)?;
+                }
+                MainPhaseAction::PlayEvent(card) => self.play_event(io, card, player)?,
+                MainPhaseAction::MoveCharacter(from) => {
+                    let destinations = self.move_destinations(from, player);
+                    let to = io.ask_required_choice(
+                        &destinations,
+                        player,
+                        ChoiceContext::MainPhaseMoveDestination,
+                    );
+                    self.move_character(io, from, destinations[to], player);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Processes the climax phase.
+    ///
+    /// 1) Publishes a Phase change with the value `Phase::Climax`
+    ///
+    /// 2) Asks the active player to choose a climax to play, or no card.
+    ///
+    /// 3) If a card is chosen, it is put into the climax zone.
+    fn climax_phase<T: RulesEngineIO>(&mut self, io: &mut T) {
+        self.phase_change(io, Phase::Climax);
+
+        let player = self.state.active_player;
+        let climaxes = self.playable_climaxes(player);
+        let card = io.ask_optional_choice(&climaxes, player, ChoiceContext::ClimaxPhaseCardToPlay);
+
+        if let Some(card) = card {
+            let card = self.state.players[player]
+                .play_climax(climaxes[card])
+                .unwrap();
+            io.play_climax(card, player);
+        }
+    }
+
+    /// Processes the end phase.
     ///
     /// 1) Publishes a Phase change with the value `Phase::End`
     ///
     /// 2) Checks the active player's handlimit.
     ///
-    /// 3) Switches turns.
+    /// 3) Puts the active player's climax into the waiting room.
+    ///
+    /// 4) Ends every until end of turn modifier.
+    ///
+    /// 5) Switches turns.
     fn end_phase<T: RulesEngineIO>(&mut self, io: &mut T) {
         self.phase_change(io, Phase::End);
 
         self.check_handlimit(io, self.state.active_player);
 
+        self.active_player_mut().clear_climax();
+
+        for player in self.state.players.iter_mut() {
+            player.stage.clear_turn_modifiers();
+        }
+
         self.switch_turns();
     }
 
@@ -112,31 +303,197 @@ impl Rules {
     /// Postcondition: A clock event will be published,
     /// the requested card will be on top of the player's clock,
     /// and the player will attempt to draw two cards.
-    fn clock_card<T: RulesEngineIO>(&mut self, io: &mut T, card: CardId, player: usize) {
+    fn clock_card<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        card: CardId,
+        player: usize,
+    ) -> Result<(), GameResult> {
         let player_state = &mut self.state.players[player];
         let card = player_state.hand.take_card_id(card).unwrap();
         player_state.clock.put_on_top(card);
-        self.interrupt_type_rules_processing(io);
+        self.interrupt_type_rules_processing(io)?;
 
         io.clock(card, player);
 
-        self.draw_card(io, player);
-        self.draw_card(io, player);
+        self.draw_card(io, player)?;
+        self.draw_card(io, player)?;
+
+        Ok(())
     }
 
-    /// Clocks a card for the specified player, to draw two cards.
-    ///
-    /// Precondition: The `player`'s deck has at least one card in it.
+    /// Draws a card for the specified player.
     ///
     /// Postcondition: A draw event will be published,
     /// and the top card of the `player`'s deck will be drawn
-    /// into their hand.
-    fn draw_card<T: RulesEngineIO>(&mut self, io: &mut T, player: usize) {
+    /// into their hand.  If the deck was empty beforehand, or
+    /// that emptied the deck, the player will refresh.
+    fn draw_card<T: RulesEngineIO>(&mut self, io: &mut T, player: usize) -> Result<(), GameResult> {
+        self.interrupt_type_rules_processing(io)?;
+
         let player_state = &mut self.state.players[player];
         let card = player_state
             .draw_card()
             .expect("can't draw a card on an empty deck");
         io.draw(card, player);
+
+        self.interrupt_type_rules_processing(io)?;
+
+        Ok(())
+    }
+
+    /// Plays a character for the specified player.
+    ///
+    /// Precondition: `card` must exist in `player`'s hand.
+    ///
+    /// Precondition: The `player` is able to pay the cost of `card`.
+    ///
+    /// Postcondition: The cost of `card` will be paid, the requested
+    /// card will be standing in the `position` on the `player`'s stage,
+    /// and a play character event will be published.  Any character
+    /// that was already in `position` is put into the waiting room.
+    /// The `card`'s abilities that trigger on being played are pending.
+    fn play_character<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        card: CardId,
+        position: StagePosition,
+        player: usize,
+    ) -> Result<(), GameResult> {
+        let cost = self.play_cost(card);
+        self.pay_cost(io, &cost, card, player)?;
+
+        let player_state = &mut self.state.players[player];
+        player_state.play_character(card, position).unwrap();
+
+        io.play_character(card, position, player);
+        self.raise_trigger(Trigger::Play, card, player);
+
+        Ok(())
+    }
+
+    /// Returns every action the `player` can take during the main phase.
+    ///
+    /// `MainPhaseAction::Pass` is always the first action.
+    fn main_phase_actions(&self, player: usize) -> Vec<MainPhaseAction> {
+        let mut actions = vec![MainPhaseAction::Pass];
+
+        actions.extend(
+            self.state.players[player]
+                .hand
+                .content
+                .iter()
+                .filter(|card| self.can_play_character(**card, player))
+                .map(|card| MainPhaseAction::PlayCharacter(*card)),
+        );
+
+        actions.extend(
+            self.state.players[player]
+                .hand
+                .content
+                .iter()
+                .filter(|card| self.can_play_event(**card, player))
+                .map(|card| MainPhaseAction::PlayEvent(*card)),
+        );
+
+        actions.extend(
+            StagePosition::ALL
+                .iter()
+                .filter(|position| !self.move_destinations(**position, player).is_empty())
+                .map(|position| MainPhaseAction::MoveCharacter(*position)),
+        );
+
+        actions
+    }
+
+    /// Returns every position the `player`'s character in `from` can move to.
+    ///
+    /// A character can move to an empty position, or swap with another
+    /// character, as long as neither of them can't move.
+    fn move_destinations(&self, from: StagePosition, player: usize) -> Vec<StagePosition> {
+        let stage = &self.state.players[player].stage;
+        let can_move = |position| {
+            stage.get(position).is_none_or(|character| {
+                !self
+                    .card_data(character.card)
+                    .abilities
+                    .contains(&Ability::CannotMove)
+            })
+        };
+
+        if stage.is_empty(from) || !can_move(from) {
+            return Vec::new();
+        }
+
+        StagePosition::ALL
+            .iter()
+            .copied()
+            .filter(|to| *to != from && can_move(*to))
+            .collect()
+    }
+
+    /// Moves the `player`'s character in `from` to `to`, swapping it with
+    /// any character already there, and publishes a move event.
+    fn move_character<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        from: StagePosition,
+        to: StagePosition,
+        player: usize,
+    ) {
+        self.state.players[player].stage.swap(from, to).unwrap();
+        io.move_character(from, to, player);
+    }
+
+    /// Checks to see if `card` is a character the `player` is able to play,
+    /// and pay the cost of.
+    fn can_play_character(&self, card: CardId, player: usize) -> bool {
+        self.card_data(card).card_type == CardType::Character
+            && self.meets_play_requirements(card, player)
+            && self.can_pay_cost(&self.play_cost(card), card, player)
+    }
+
+    /// Returns every climax in the `player`'s hand they are able to play.
+    ///
+    /// If the `player` already has a climax in their climax zone, none can be played.
+    fn playable_climaxes(&self, player: usize) -> Vec<CardId> {
+        let player_state = &self.state.players[player];
+        if !player_state.climax.content.is_empty() {
+            return Vec::new();
+        }
+
+        player_state
+            .hand
+            .content
+            .iter()
+            .filter(|card| self.card_data(**card).card_type == CardType::Climax)
+            .filter(|card| self.meets_play_requirements(**card, player))
+            .copied()
+            .collect()
+    }
+
+    /// Checks to see if the `player` meets the level and color
+    /// requirements to play `card`.
+    ///
+    /// A card's level can't be higher than the player's level, and
+    /// climaxes and cards above level 0 need a card of the same
+    /// color in the player's level or clock zones.
+    fn meets_play_requirements(&self, card: CardId, player: usize) -> bool {
+        let player_state = &self.state.players[player];
+        let data = self.card_data(card);
+        let stats = &data.base_stats;
+
+        if stats.level > player_state.current_level() {
+            return false;
+        }
+
+        (stats.level == 0 && data.card_type != CardType::Climax)
+            || player_state
+                .level
+                .content
+                .iter()
+                .chain(player_state.clock.content.iter())
+                .any(|card| self.card_data(*card).base_stats.color == stats.color)
     }
 
     fn phase_change<T: RulesEngineIO>(&mut self, io: &mut T, phase: Phase) {
@@ -170,87 +527,99 @@ impl Rules {
     /// before.  If it is now the first player's turn, then
     /// the turn counter is incremented by 1.
     fn switch_turns(&mut self) {
-        if self.state.active_player == 0 {
-            self.state.active_player = 1;
-        } else if self.state.active_player == 1 {
-            self.state.active_player = 0;
+        self.state.active_player = self.state.non_active_player();
+        if self.state.active_player == self.state.first_player {
             self.state.turn += 1;
         }
     }
+}
 
-    /// Processes interrupt-type rules.
-    ///
-    /// Postconditions: both players have valid game states
-    /// in regards to level-up rules processing (i.e. they
-    /// both have less than 7 cards in clock).
-    fn interrupt_type_rules_processing<T: RulesEngineIO>(&mut self, io: &mut T) {
-        loop {
-            let mut done = true;
-
-            for player in [self.state.active_player(), self.state.non_active_player()].iter() {
-                if self.state.players[*player].needs_to_level()
-                    && self.state.players[*player].needs_to_refresh()
-                {
-                    let choice = io.ask_required_choice(
-                        &[InterruptChoice::Level, InterruptChoice::Refresh],
-                        *player,
-                        ChoiceContext::InterruptTimingChoice,
-                    );
-
-                    match [InterruptChoice::Level, InterruptChoice::Refresh][choice] {
-                        InterruptChoice::Level => {
-                            self.level_player(io, *player);
-                        }
-                        InterruptChoice::Refresh => {
-                            assert_eq!(self.state.players[*player].refresh().unwrap(), false);
-
-                            io.refreshed(*player);
-                        }
-                    }
-
-                    done = false;
-                } else {
-                    if self.state.players[*player].needs_to_level() {
-                        done = false;
-                        self.level_player(io, *player);
-                    }
-
-                    if self.state.players[*player].needs_to_refresh() {
-                        done = false;
-
-                        assert_eq!(self.state.players[*player].refresh().unwrap(), false);
+#[cfg(test)]
+impl Rules {
+    /// Creates a new card owned by the `owner`, outside of every zone,
+    /// from its own copy of the default card data.
+    fn new_card(&mut self, owner: usize) -> CardId {
+        self.data.cards.push(CardData::default());
+        let data = crate::data::CardDataId(self.data.cards.len() - 1);
+        self.state.add_card(owner, data)
+    }
 
-                        io.refreshed(*player);
-                    }
-                }
-            }
+    /// Creates `count` new cards owned by the `owner`, like [`Rules::new_card`].
+    fn new_cards(&mut self, owner: usize, count: usize) -> Vec<CardId> {
+        (0..count).map(|_| self.new_card(owner)).collect()
+    }
 
-            if done {
-                return;
-            }
-        }
+    /// Creates a new card owned by the `owner`, like [`Rules::new_card`],
+    /// and puts it into the `position` on their stage.
+    fn new_character(&mut self, owner: usize, position: StagePosition) -> CardId {
+        let card = self.new_card(owner);
+        self.state.players[owner]
+            .stage
+            .put_in_slot(position, card)
+            .unwrap();
+        card
     }
-    /// Levels up the `player`.
-    ///
-    /// Postcondition: The `player`'s clock is cleared of
-    /// the bottom 7 cards, and one card is chosen to be put
-    /// in the level zone.  The rest are put in the waiting
-    /// room of that player.  A level up event is emitted.
-    fn level_player<T: RulesEngineIO>(&mut self, io: &mut T, player: usize) {
-        let player_state = &mut self.state.players[player];
-        let bottom_clock = &player_state.clock.content[0..7];
-        let card_idx = io.ask_required_choice(bottom_clock, player, ChoiceContext::LevelUpProcess);
-        let card = bottom_clock[card_idx];
 
-        let result = player_state.level_up_with(card).unwrap();
-        io.level_up(result, player);
+    /// Returns the definition the `card` was created from, so tests can change it.
+    fn card_data_mut(&mut self, card: CardId) -> &mut CardData {
+        let data = self.state.card(card).data;
+        &mut self.data.cards[data.0]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::card_data::Effect;
     use crate::rules::io::PickX;
+    use crate::state::player_state::TriggeredAbility;
+
+    use crate::data::card_database::SAMPLE_CARDS;
+    use crate::data::deck_list::{DECK_SIZE, SAMPLE_DECK};
+
+    #[test]
+    fn card_data() {
+        let mut data = GameData::with_default_cards(2);
+        data.cards[1].name = "Named".to_string();
+        data.cards[1].base_stats.level = 2;
+        let deck = DeckList {
+            cards: vec![(0.into(), 1), (1.into(), 1)],
+        };
+        let rules = Rules::with_decks(data, [&deck, &deck], 0);
+
+        assert_eq!(rules.card_data(1.into()).name, "Named");
+        assert_eq!(rules.card_data(1.into()).base_stats.level, 2);
+        assert_eq!(rules.card_data(0.into()), &CardData::default());
+        assert_eq!(rules.card_data(3.into()).name, "Named");
+    }
+
+    #[test]
+    fn run_game_with_sample_decks() {
+        let data = GameData::parse(SAMPLE_CARDS).unwrap();
+        let deck = DeckList::parse(SAMPLE_DECK, &data).unwrap();
+        let mut rules = Rules::with_decks(data, [&deck, &deck], 3);
+
+        rules.run_game(&mut (), None);
+
+        assert_eq!(rules.state.cards.len(), 2 * DECK_SIZE);
+    }
+
+    #[test]
+    fn card_data_shared_between_copies() {
+        let mut rules = Rules::new();
+        rules.data.cards[3].name = "Shared".to_string();
+        let copies: Vec<_> = rules
+            .state
+            .players
+            .iter()
+            .map(|player| player.deck.content[3])
+            .collect();
+
+        assert_ne!(copies[0], copies[1]);
+        for card in copies {
+            assert_eq!(rules.card_data(card).name, "Shared");
+        }
+    }
 
     #[test]
     fn switch_turns() {
@@ -275,6 +644,23 @@ mod tests {
         assert_eq!(rules.state.turn, 1);
     }
 
+    #[test]
+    fn switch_turns_second_player_first() {
+        let mut rules = Rules::new();
+        rules.state.first_player = 1;
+        rules.state.active_player = 1;
+
+        rules.switch_turns();
+
+        assert_eq!(rules.state.active_player, 0);
+        assert_eq!(rules.state.turn, 0);
+
+        rules.switch_turns();
+
+        assert_eq!(rules.state.active_player, 1);
+        assert_eq!(rules.state.turn, 1);
+    }
+
     #[test]
     fn end_phase() {
         let mut rules = Rules::new();
@@ -298,6 +684,89 @@ mod tests {
         assert_eq!(rules.state.turn, 1);
     }
 
+    #[test]
+    fn stand_phase() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.new_character(player, StagePosition::CenterLeft);
+        rules.new_character(player, StagePosition::CenterMiddle);
+        let does_not_stand = rules.new_character(player, StagePosition::CenterRight);
+        rules.new_character(player, StagePosition::BackLeft);
+        rules.card_data_mut(does_not_stand).abilities = vec![Ability::DoesNotStand];
+
+        let stage = &mut rules.active_player_mut().stage;
+        stage.rest(StagePosition::CenterLeft).unwrap();
+        stage.reverse(StagePosition::CenterMiddle).unwrap();
+        stage.rest(StagePosition::CenterRight).unwrap();
+
+        let opponent = rules.state.non_active_player();
+        rules.new_character(opponent, StagePosition::CenterLeft);
+        rules.state.players[opponent]
+            .stage
+            .rest(StagePosition::CenterLeft)
+            .unwrap();
+
+        rules.stand_phase(&mut ());
+
+        let stage = &rules.active_player().stage;
+        let status = |position| stage.get(position).unwrap().status;
+        assert_eq!(status(StagePosition::CenterLeft), CharacterStatus::Standing);
+        assert_eq!(
+            status(StagePosition::CenterMiddle),
+            CharacterStatus::Standing
+        );
+        assert_eq!(status(StagePosition::CenterRight), CharacterStatus::Rested);
+        assert_eq!(status(StagePosition::BackLeft), CharacterStatus::Standing);
+        assert_eq!(
+            rules.state.players[opponent]
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .status,
+            CharacterStatus::Rested
+        );
+    }
+
+    #[test]
+    fn stand_phase_triggers() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let rested = rules.active_player_mut().deck.take_top().unwrap();
+        let standing = rules.active_player_mut().deck.take_top().unwrap();
+        for card in [rested, standing].iter() {
+            rules.card_data_mut(*card).abilities = vec![Ability::Auto {
+                trigger: Trigger::Stand,
+                effect: Effect::Draw(1),
+            }];
+        }
+
+        let stage = &mut rules.active_player_mut().stage;
+        stage
+            .put_in_slot(StagePosition::CenterLeft, rested)
+            .unwrap();
+        stage
+            .put_in_slot(StagePosition::CenterMiddle, standing)
+            .unwrap();
+        stage.rest(StagePosition::CenterLeft).unwrap();
+
+        rules.stand_phase(&mut ());
+
+        assert_eq!(
+            rules.active_player().pending_abilities,
+            vec![TriggeredAbility {
+                source: rested,
+                ability: 0
+            }]
+        );
+
+        rules.draw_phase(&mut ()).unwrap();
+
+        assert!(rules.active_player().pending_abilities.is_empty());
+        assert_eq!(rules.state.players[player].hand.content.len(), 2);
+    }
+
     #[test]
     fn draw_phase() {
         let mut rules = Rules::new();
@@ -305,7 +774,7 @@ mod tests {
         let starting_hand_size = rules.active_player().hand.content.len();
         let starting_deck_size = rules.active_player().deck.content.len();
 
-        rules.draw_phase(&mut ());
+        rules.draw_phase(&mut ()).unwrap();
 
         assert_eq!(
             rules.active_player().hand.content.len(),
@@ -325,7 +794,7 @@ mod tests {
         let starting_deck_size = rules.active_player().deck.content.len();
         let starting_clock_size = rules.active_player().clock.content.len();
 
-        rules.clock_phase(&mut ());
+        rules.clock_phase(&mut ()).unwrap();
 
         assert_eq!(rules.active_player().hand.content.len(), starting_hand_size);
         assert_eq!(rules.active_player().deck.content.len(), starting_deck_size);
@@ -344,7 +813,7 @@ mod tests {
 
         rules.active_player_mut().draw_card().unwrap();
 
-        rules.clock_phase(&mut ());
+        rules.clock_phase(&mut ()).unwrap();
 
         assert_eq!(
             rules.active_player().hand.content.len(),
@@ -370,7 +839,9 @@ mod tests {
         let starting_clock_size = rules.active_player().clock.content.len();
         let clocked_card = rules.active_player().hand.content[0];
 
-        rules.clock_card(&mut (), clocked_card, rules.state.active_player);
+        rules
+            .clock_card(&mut (), clocked_card, rules.state.active_player)
+            .unwrap();
 
         // plus 2 cards - 1
         assert_eq!(
@@ -387,12 +858,233 @@ mod tests {
         );
     }
 
+    #[test]
+    fn main_phase_pass() {
+        let mut rules = Rules::new();
+
+        rules.active_player_mut().draw_card().unwrap();
+        let starting_hand_size = rules.active_player().hand.content.len();
+
+        rules.main_phase(&mut ()).unwrap();
+
+        assert_eq!(rules.state.phase, Phase::Main);
+        assert_eq!(rules.active_player().hand.content.len(), starting_hand_size);
+        assert_eq!(rules.active_player().stage.characters().count(), 0);
+    }
+
+    #[test]
+    fn main_phase_actions() {
+        let mut rules = Rules::new();
+
+        assert_eq!(
+            rules.main_phase_actions(rules.state.active_player),
+            vec![MainPhaseAction::Pass]
+        );
+
+        let card = rules.active_player_mut().draw_card().unwrap();
+
+        assert_eq!(
+            rules.main_phase_actions(rules.state.active_player),
+            vec![MainPhaseAction::Pass, MainPhaseAction::PlayCharacter(card)]
+        );
+    }
+
+    #[test]
+    fn play_requirements() {
+        use crate::data::card_data::Color;
+
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let card = rules.new_card(player);
+        rules.card_data_mut(card).base_stats.level = 1;
+        rules.card_data_mut(card).base_stats.color = Color::Red;
+
+        assert!(!rules.can_play_character(card, player));
+
+        let level = rules.new_card(player);
+        rules.active_player_mut().level.put_on_top(level);
+        assert!(!rules.can_play_character(card, player));
+
+        let clock = rules.new_card(player);
+        rules.card_data_mut(clock).base_stats.color = Color::Red;
+        rules.active_player_mut().clock.put_on_top(clock);
+        assert!(rules.can_play_character(card, player));
+    }
+
+    #[test]
+    fn play_character_cost() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let card = rules.new_card(player);
+        rules.card_data_mut(card).base_stats.cost = 1;
+        rules.active_player_mut().hand.put_on_top(card);
+
+        assert!(!rules.can_play_character(card, player));
+
+        let stock = rules.new_card(player);
+        rules.active_player_mut().stock.put_on_top(stock);
+        assert!(rules.can_play_character(card, player));
+
+        rules
+            .play_character(&mut (), card, StagePosition::CenterLeft, player)
+            .unwrap();
+
+        assert!(rules.active_player().stock.content.is_empty());
+        assert_eq!(rules.active_player().waiting_room.content, vec![stock]);
+    }
+
+    #[test]
+    fn move_destinations() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.new_character(player, StagePosition::CenterLeft);
+        rules.new_character(player, StagePosition::CenterMiddle);
+        let cannot_move = rules.new_character(player, StagePosition::CenterRight);
+        rules.card_data_mut(cannot_move).abilities = vec![Ability::CannotMove];
+
+        assert_eq!(
+            rules.move_destinations(StagePosition::CenterLeft, player),
+            vec![
+                StagePosition::CenterMiddle,
+                StagePosition::BackLeft,
+                StagePosition::BackRight
+            ]
+        );
+        assert!(rules
+            .move_destinations(StagePosition::CenterRight, player)
+            .is_empty());
+        assert!(rules
+            .move_destinations(StagePosition::BackLeft, player)
+            .is_empty());
+        assert_eq!(
+            rules.main_phase_actions(player),
+            vec![
+                MainPhaseAction::Pass,
+                MainPhaseAction::MoveCharacter(StagePosition::CenterLeft),
+                MainPhaseAction::MoveCharacter(StagePosition::CenterMiddle)
+            ]
+        );
+    }
+
+    #[test]
+    fn move_character() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let left = rules.new_character(player, StagePosition::CenterLeft);
+        let middle = rules.new_character(player, StagePosition::CenterMiddle);
+
+        rules.move_character(
+            &mut (),
+            StagePosition::CenterLeft,
+            StagePosition::CenterMiddle,
+            player,
+        );
+        rules.move_character(
+            &mut (),
+            StagePosition::CenterLeft,
+            StagePosition::BackRight,
+            player,
+        );
+
+        let stage = &rules.active_player().stage;
+        assert!(stage.is_empty(StagePosition::CenterLeft));
+        assert_eq!(stage.get(StagePosition::CenterMiddle).unwrap().card, left);
+        assert_eq!(stage.get(StagePosition::BackRight).unwrap().card, middle);
+    }
+
+    #[test]
+    fn climax_phase() {
+        use crate::data::card_data::Color;
+
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let climax = rules.new_card(player);
+        rules.card_data_mut(climax).card_type = CardType::Climax;
+        rules.card_data_mut(climax).base_stats.color = Color::Blue;
+        let character = rules.new_card(player);
+        rules.active_player_mut().hand.content = vec![climax, character];
+
+        assert_eq!(
+            rules.main_phase_actions(player),
+            vec![
+                MainPhaseAction::Pass,
+                MainPhaseAction::PlayCharacter(character)
+            ]
+        );
+        assert_eq!(rules.playable_climaxes(player), vec![]);
+
+        let clock = rules.new_card(player);
+        rules.card_data_mut(clock).base_stats.color = Color::Blue;
+        rules.active_player_mut().clock.put_on_top(clock);
+        assert_eq!(rules.playable_climaxes(player), vec![climax]);
+
+        rules.climax_phase(&mut ());
+
+        assert_eq!(rules.state.phase, Phase::Climax);
+        assert_eq!(rules.active_player().climax.content, vec![climax]);
+        assert_eq!(rules.active_player().hand.content, vec![character]);
+
+        rules.end_phase(&mut ());
+
+        assert!(rules.state.players[player].climax.content.is_empty());
+        assert_eq!(
+            rules.state.players[player].waiting_room.content,
+            vec![climax]
+        );
+    }
+
+    #[test]
+    fn play_character() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let card = rules.active_player_mut().draw_card().unwrap();
+
+        rules
+            .play_character(&mut (), card, StagePosition::CenterMiddle, player)
+            .unwrap();
+
+        assert!(rules.active_player().hand.content.is_empty());
+        assert_eq!(
+            rules.active_player().stage.position_of(card),
+            Some(StagePosition::CenterMiddle)
+        );
+    }
+
+    #[test]
+    fn play_character_triggers() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        let card = rules.active_player_mut().draw_card().unwrap();
+        rules.card_data_mut(card).abilities = vec![Ability::Auto {
+            trigger: Trigger::Play,
+            effect: Effect::Draw(1),
+        }];
+
+        rules
+            .play_character(&mut (), card, StagePosition::CenterMiddle, player)
+            .unwrap();
+
+        assert_eq!(rules.active_player().pending_abilities.len(), 1);
+
+        rules.check_timing(&mut ()).unwrap();
+
+        assert!(rules.active_player().pending_abilities.is_empty());
+        assert_eq!(rules.active_player().hand.content.len(), 1);
+    }
+
     #[test]
     fn check_handlimit() {
         let mut rules = Rules::new();
 
         for _ in 0..10 {
-            rules.draw_card(&mut (), rules.state.active_player);
+            rules.draw_card(&mut (), rules.state.active_player).unwrap();
         }
         let starting_hand_size = rules.active_player().hand.content.len();
         let starting_waiting_room_size = rules.active_player().waiting_room.content.len();
@@ -410,14 +1102,13 @@ mod tests {
     #[test]
     fn check_leveling_up() {
         let mut rules = Rules::new();
+        let player = rules.state.active_player;
 
-        for i in 0..14 {
-            rules.active_player_mut().clock.put_on_top(i.into());
-        }
+        rules.active_player_mut().clock.content = rules.new_cards(player, 14);
 
         let starting_level = rules.active_player().level.content.len();
         let starting_waiting_room_size = rules.active_player().waiting_room.content.len();
-        rules.interrupt_type_rules_processing(&mut ());
+        rules.interrupt_type_rules_processing(&mut ()).unwrap();
 
         assert_eq!(
             rules.active_player().level.content.len(),
@@ -432,17 +1123,16 @@ mod tests {
     #[test]
     fn check_refreshing() {
         let mut rules = Rules::new();
+        let player = rules.state.active_player;
 
         rules.active_player_mut().deck.content.clear();
 
-        for i in 0..14 {
-            rules.active_player_mut().waiting_room.put_on_top(i.into());
-        }
+        rules.active_player_mut().waiting_room.content = rules.new_cards(player, 14);
 
         let starting_waiting_room_size = dbg!(rules.active_player().waiting_room.content.len());
         let starting_deck_size = rules.active_player().deck.content.len();
 
-        rules.interrupt_type_rules_processing(&mut ());
+        rules.interrupt_type_rules_processing(&mut ()).unwrap();
 
         assert_eq!(
             rules.active_player().deck.content.len(),
@@ -460,18 +1150,17 @@ mod tests {
     #[test]
     fn check_level_then_refresh() {
         let mut rules = Rules::new();
+        let player = rules.state.active_player;
 
         rules.active_player_mut().deck.content.clear();
 
-        for i in 0..14 {
-            rules.active_player_mut().waiting_room.put_on_top(i.into());
-            rules.active_player_mut().clock.put_on_top(i.into());
-        }
+        rules.active_player_mut().waiting_room.content = rules.new_cards(player, 14);
+        rules.active_player_mut().clock.content = rules.new_cards(player, 14);
 
         let starting_deck_size = rules.active_player().deck.content.len();
         let starting_level = rules.active_player().level.content.len();
 
-        rules.interrupt_type_rules_processing(&mut ());
+        rules.interrupt_type_rules_processing(&mut ()).unwrap();
 
         assert_eq!(
             rules.active_player().deck.content.len(),
@@ -491,18 +1180,19 @@ mod tests {
     #[test]
     fn check_refresh_then_level() {
         let mut rules = Rules::new();
+        let player = rules.state.active_player;
 
         rules.active_player_mut().deck.content.clear();
 
-        for i in 0..7 {
-            rules.active_player_mut().waiting_room.put_on_top(i.into());
-            rules.active_player_mut().clock.put_on_top(i.into());
-        }
+        rules.active_player_mut().waiting_room.content = rules.new_cards(player, 7);
+        rules.active_player_mut().clock.content = rules.new_cards(player, 7);
 
         let starting_deck_size = rules.active_player().deck.content.len();
         let starting_level = rules.active_player().level.content.len();
 
-        rules.interrupt_type_rules_processing(&mut PickX(1));
+        rules
+            .interrupt_type_rules_processing(&mut PickX(1))
+            .unwrap();
 
         assert_eq!(
             rules.active_player().deck.content.len(),
@@ -518,4 +1208,138 @@ mod tests {
 
         assert_eq!(rules.active_player().refresh_point, 1);
     }
+
+    #[test]
+    fn level_four_loss() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().level.content = rules.new_cards(player, 3);
+        rules.active_player_mut().clock.content = rules.new_cards(player, 7);
+
+        assert_eq!(
+            rules.interrupt_type_rules_processing(&mut ()),
+            Err(GameResult::Win {
+                winner: 1 - player,
+                reason: LossReason::LevelFour
+            })
+        );
+    }
+
+    #[test]
+    fn empty_refresh_loss() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content.clear();
+
+        assert_eq!(
+            rules.interrupt_type_rules_processing(&mut ()),
+            Err(GameResult::Win {
+                winner: 1 - player,
+                reason: LossReason::NoCardsToRefresh
+            })
+        );
+        assert_eq!(rules.active_player().refresh_point, 0);
+    }
+
+    #[test]
+    fn level_before_empty_refresh() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content.clear();
+        rules.active_player_mut().clock.content = rules.new_cards(player, 7);
+
+        rules.interrupt_type_rules_processing(&mut ()).unwrap();
+
+        assert_eq!(rules.active_player().level.content.len(), 1);
+        assert_eq!(rules.active_player().refresh_point, 1);
+    }
+
+    #[test]
+    fn simultaneous_loss_draw() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content.clear();
+        let opponent = 1 - player;
+        rules.state.players[opponent].level.content = rules.new_cards(opponent, 3);
+        rules.state.players[opponent].clock.content = rules.new_cards(opponent, 7);
+
+        let mut reasons = [LossReason::NoCardsToRefresh; 2];
+        reasons[1 - player] = LossReason::LevelFour;
+        assert_eq!(
+            rules.interrupt_type_rules_processing(&mut ()),
+            Err(GameResult::Draw { reasons })
+        );
+    }
+
+    #[test]
+    fn run_turn_stops_on_loss() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content = rules.new_cards(player, 1);
+
+        assert_eq!(
+            rules.run_turn(&mut ()),
+            Some(GameResult::Win {
+                winner: 1 - player,
+                reason: LossReason::NoCardsToRefresh
+            })
+        );
+        assert_eq!(rules.state.phase, Phase::Draw);
+        assert_eq!(rules.state.active_player, player);
+    }
+
+    #[test]
+    fn check_timing_refresh_damage() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content.clear();
+        rules.active_player_mut().waiting_room.content = rules.new_cards(player, 3);
+
+        rules.check_timing(&mut ()).unwrap();
+
+        assert_eq!(rules.active_player().refresh_point, 0);
+        assert_eq!(rules.active_player().deck.content.len(), 2);
+        assert_eq!(rules.active_player().clock.content.len(), 1);
+    }
+
+    #[test]
+    fn refresh_damage_level_up() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content.clear();
+        rules.active_player_mut().waiting_room.content = rules.new_cards(player, 3);
+        rules.active_player_mut().clock.content = rules.new_cards(player, 6);
+
+        rules.check_timing(&mut ()).unwrap();
+
+        assert_eq!(rules.active_player().level.content.len(), 1);
+        assert_eq!(rules.active_player().clock.content.len(), 0);
+        assert_eq!(rules.active_player().waiting_room.content.len(), 6);
+    }
+
+    #[test]
+    fn draw_from_empty_deck() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+
+        rules.active_player_mut().deck.content.clear();
+        rules.active_player_mut().waiting_room.content = rules.new_cards(player, 3);
+        let starting_hand_size = rules.active_player().hand.content.len();
+
+        rules.draw_card(&mut (), player).unwrap();
+
+        assert_eq!(
+            rules.active_player().hand.content.len(),
+            starting_hand_size + 1
+        );
+        assert_eq!(rules.active_player().deck.content.len(), 2);
+        assert_eq!(rules.active_player().refresh_point, 1);
+    }
 }
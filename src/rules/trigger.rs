@@ -0,0 +1,438 @@
+use super::attack::Attack;
+use super::io::{ChoiceContext, ChoiceTriggerDestination, RulesEngineIO};
+use super::{GameResult, Rules};
+use crate::data::card_data::{CardType, TriggerIcon};
+use crate::data::CardId;
+use crate::state::stage_state::StagePosition;
+
+impl Rules {
+    /// Processes the trigger step of the `attack`.
+    ///
+    /// 1) Puts the top card of the active player's deck into their
+    ///    resolution zone, and publishes a trigger event.
+    ///
+    /// 2) Resolves each trigger icon on the revealed card.
+    ///
+    /// 3) Puts the revealed card into the active player's stock.
+    pub(super) fn trigger_step<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        attack: &mut Attack,
+    ) -> Result<(), GameResult> {
+        let player = self.state.active_player;
+
+        let player_state = &mut self.state.players[player];
+        let card = player_state
+            .deck
+            .take_top()
+            .expect("can't trigger a card on an empty deck");
+        player_state.resolution.put_on_top(card);
+        io.trigger(card, player);
+
+        self.interrupt_type_rules_processing(io)?;
+
+        for icon in self.card_data(card).triggers.clone() {
+            self.resolve_trigger_icon(io, icon, attack)?;
+        }
+
+        let player_state = &mut self.state.players[player];
+        let card = player_state.resolution.take_card_id(card).unwrap();
+        player_state.stock.put_on_top(card);
+
+        Ok(())
+    }
+
+    /// Resolves the effect of a single trigger `icon` for the active player.
+    ///
+    /// Postcondition: If the effect happened, a trigger effect event is published.
+    fn resolve_trigger_icon<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        icon: TriggerIcon,
+        attack: &mut Attack,
+    ) -> Result<(), GameResult> {
+        let player = self.state.active_player;
+
+        match icon {
+            TriggerIcon::Soul => {
+                if let Some(attacker) = self.state.players[player].stage.get_mut(attack.attacker) {
+                    attacker.turn_modifiers.soul += 1;
+                    io.trigger_effect(icon, None, player);
+                }
+            }
+            TriggerIcon::Shot => {
+                attack.shot = true;
+                io.trigger_effect(icon, None, player);
+            }
+            TriggerIcon::Pool => {
+                if self.confirm_trigger(io, icon) {
+                    let player_state = &mut self.state.players[player];
+                    let card = player_state
+                        .deck
+                        .take_top()
+                        .expect("can't pool a card from an empty deck");
+                    player_state.stock.put_on_top(card);
+                    io.trigger_effect(icon, None, player);
+
+                    self.interrupt_type_rules_processing(io)?;
+                }
+            }
+            TriggerIcon::Draw => {
+                io.trigger_effect(icon, None, player);
+                self.draw_card(io, player)?;
+            }
+            TriggerIcon::Book => {
+                if self.confirm_trigger(io, icon) {
+                    io.trigger_effect(icon, None, player);
+                    self.draw_card(io, player)?;
+                }
+            }
+            TriggerIcon::Bounce => {
+                let opponent = self.state.non_active_player();
+                let targets: Vec<_> = self.state.players[opponent]
+                    .stage
+                    .characters()
+                    .map(|(position, _)| position)
+                    .collect();
+                let target = io.ask_optional_choice(
+                    &targets,
+                    player,
+                    ChoiceContext::TriggerStepBounceTarget,
+                );
+
+                if let Some(target) = target {
+                    let opponent_state = &mut self.state.players[opponent];
+                    let card = opponent_state.character_to_hand(targets[target]).unwrap();
+                    io.trigger_effect(icon, Some(card), player);
+                }
+            }
+            TriggerIcon::Gate => {
+                let targets = self.waiting_room_cards(player, |rules, card| {
+                    rules.card_data(card).card_type == CardType::Climax
+                });
+                self.return_to_hand(io, icon, &targets, ChoiceContext::TriggerStepGateTarget);
+            }
+            TriggerIcon::Door => {
+                let targets = self.waiting_room_cards(player, |rules, card| {
+                    rules.card_data(card).card_type == CardType::Character
+                });
+                self.return_to_hand(io, icon, &targets, ChoiceContext::TriggerStepDoorTarget);
+            }
+            TriggerIcon::Choice => {
+                let targets = self.waiting_room_cards(player, |rules, card| {
+                    let data = rules.card_data(card);
+                    data.card_type == CardType::Character
+                        && data.triggers.contains(&TriggerIcon::Soul)
+                });
+                let target = io.ask_optional_choice(
+                    &targets,
+                    player,
+                    ChoiceContext::TriggerStepChoiceTarget,
+                );
+
+                if let Some(target) = target {
+                    let destinations = [
+                        ChoiceTriggerDestination::Hand,
+                        ChoiceTriggerDestination::Stock,
+                    ];
+                    let destination = io.ask_required_choice(
+                        &destinations,
+                        player,
+                        ChoiceContext::TriggerStepChoiceDestination,
+                    );
+
+                    let player_state = &mut self.state.players[player];
+                    let card = player_state
+                        .waiting_room
+                        .take_card_id(targets[target])
+                        .unwrap();
+                    match destinations[destination] {
+                        ChoiceTriggerDestination::Hand => player_state.hand.put_on_top(card),
+                        ChoiceTriggerDestination::Stock => player_state.stock.put_on_top(card),
+                    }
+                    io.trigger_effect(icon, Some(card), player);
+                }
+            }
+            TriggerIcon::Standby => {
+                let max_level = self.state.players[player].current_level() + 1;
+                let targets = self.waiting_room_cards(player, |rules, card| {
+                    let data = rules.card_data(card);
+                    data.card_type == CardType::Character && data.base_stats.level <= max_level
+                });
+                let target = io.ask_optional_choice(
+                    &targets,
+                    player,
+                    ChoiceContext::TriggerStepStandbyTarget,
+                );
+
+                if let Some(target) = target {
+                    let position = io.ask_required_choice(
+                        &StagePosition::ALL,
+                        player,
+                        ChoiceContext::TriggerStepStandbyPosition,
+                    );
+                    let position = StagePosition::ALL[position];
+
+                    let player_state = &mut self.state.players[player];
+                    let card = targets[target];
+                    player_state.character_to_waiting_room(position);
+                    player_state
+                        .stage
+                        .put_from_zone(position, &mut player_state.waiting_room, card)
+                        .unwrap();
+                    player_state.stage.rest(position).unwrap();
+                    io.trigger_effect(icon, Some(card), player);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Asks the active player if they want to use the optional effect of `icon`.
+    fn confirm_trigger<T: RulesEngineIO>(&mut self, io: &mut T, icon: TriggerIcon) -> bool {
+        io.ask_optional_choice(
+            &[icon],
+            self.state.active_player,
+            ChoiceContext::TriggerStepUseEffect,
+        )
+        .is_some()
+    }
+
+    /// Returns every card in the `player`'s waiting room that matches the `filter`.
+    fn waiting_room_cards<F: Fn(&Rules, CardId) -> bool>(
+        &self,
+        player: usize,
+        filter: F,
+    ) -> Vec<CardId> {
+        self.state.players[player]
+            .waiting_room
+            .content
+            .iter()
+            .filter(|card| filter(self, **card))
+            .copied()
+            .collect()
+    }
+
+    /// Asks the active player to choose one of the `targets` in their
+    /// waiting room, or no card, and returns it to their hand.
+    fn return_to_hand<T: RulesEngineIO>(
+        &mut self,
+        io: &mut T,
+        icon: TriggerIcon,
+        targets: &[CardId],
+        context: ChoiceContext,
+    ) {
+        let player = self.state.active_player;
+        let target = io.ask_optional_choice(targets, player, context);
+
+        if let Some(target) = target {
+            let player_state = &mut self.state.players[player];
+            let card = player_state
+                .waiting_room
+                .take_card_id(targets[target])
+                .unwrap();
+            player_state.hand.put_on_top(card);
+            io.trigger_effect(icon, Some(card), player);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::card_data::CardData;
+    use crate::rules::io::AttackType;
+
+    fn attack() -> Attack {
+        Attack {
+            attacker: StagePosition::CenterLeft,
+            attack_type: AttackType::Direct,
+            shot: false,
+        }
+    }
+
+    fn rules_with_trigger(icon: TriggerIcon) -> Rules {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let top = *rules.active_player().deck.content.last().unwrap();
+        rules.card_data_mut(top).triggers = vec![icon];
+        rules.new_character(player, StagePosition::CenterLeft);
+        rules
+    }
+
+    /// Creates new cards owned by the active player, with their definitions
+    /// changed by `setup`, and puts them into the waiting room in order.
+    fn waiting_room(rules: &mut Rules, setup: &[fn(&mut CardData)]) -> Vec<CardId> {
+        let player = rules.state.active_player;
+        let cards: Vec<_> = setup
+            .iter()
+            .map(|setup| {
+                let card = rules.new_card(player);
+                setup(rules.card_data_mut(card));
+                card
+            })
+            .collect();
+        rules.active_player_mut().waiting_room.content = cards.clone();
+        cards
+    }
+
+    #[test]
+    fn trigger_step_to_stock() {
+        let mut rules = Rules::new();
+        let top = *rules.active_player().deck.content.last().unwrap();
+        let starting_deck_size = rules.active_player().deck.content.len();
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(rules.active_player().stock.content, vec![top]);
+        assert!(rules.active_player().resolution.content.is_empty());
+        assert_eq!(
+            rules.active_player().deck.content.len(),
+            starting_deck_size - 1
+        );
+    }
+
+    #[test]
+    fn trigger_step_refreshes() {
+        let mut rules = Rules::new();
+        let player = rules.state.active_player;
+        let top = rules.new_card(player);
+        rules.active_player_mut().deck.content = vec![top];
+        waiting_room(&mut rules, &[|_| {}, |_| {}]);
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(rules.active_player().stock.content, vec![top]);
+        assert_eq!(rules.active_player().deck.content.len(), 2);
+        assert_eq!(rules.active_player().refresh_point, 1);
+    }
+    #[test]
+    fn soul_trigger() {
+        let mut rules = rules_with_trigger(TriggerIcon::Soul);
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(
+            rules
+                .active_player()
+                .stage
+                .get(StagePosition::CenterLeft)
+                .unwrap()
+                .turn_modifiers
+                .soul,
+            1
+        );
+    }
+
+    #[test]
+    fn shot_trigger() {
+        let mut rules = rules_with_trigger(TriggerIcon::Shot);
+        let mut attack = attack();
+
+        rules.trigger_step(&mut (), &mut attack).unwrap();
+
+        assert!(attack.shot);
+    }
+
+    #[test]
+    fn pool_trigger() {
+        let mut rules = rules_with_trigger(TriggerIcon::Pool);
+        let starting_deck_size = rules.active_player().deck.content.len();
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(rules.active_player().stock.content.len(), 2);
+        assert_eq!(
+            rules.active_player().deck.content.len(),
+            starting_deck_size - 2
+        );
+    }
+
+    #[test]
+    fn draw_triggers() {
+        for icon in [TriggerIcon::Draw, TriggerIcon::Book].iter() {
+            let mut rules = rules_with_trigger(*icon);
+
+            rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+            assert_eq!(rules.active_player().hand.content.len(), 1);
+        }
+    }
+
+    #[test]
+    fn bounce_trigger() {
+        let mut rules = rules_with_trigger(TriggerIcon::Bounce);
+        let opponent = rules.state.non_active_player();
+        let card = rules.new_character(opponent, StagePosition::CenterRight);
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert!(rules.state.players[opponent]
+            .stage
+            .is_empty(StagePosition::CenterRight));
+        assert_eq!(rules.state.players[opponent].hand.content, vec![card]);
+    }
+
+    #[test]
+    fn gate_and_door_triggers() {
+        let climax: fn(&mut CardData) = |data| data.card_type = CardType::Climax;
+
+        let mut rules = rules_with_trigger(TriggerIcon::Gate);
+        let cards = waiting_room(&mut rules, &[|_| {}, climax]);
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(rules.active_player().hand.content, vec![cards[1]]);
+
+        let mut rules = rules_with_trigger(TriggerIcon::Door);
+        let cards = waiting_room(&mut rules, &[climax, |_| {}]);
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(rules.active_player().hand.content, vec![cards[1]]);
+    }
+
+    #[test]
+    fn choice_trigger() {
+        let mut rules = rules_with_trigger(TriggerIcon::Choice);
+        let cards = waiting_room(
+            &mut rules,
+            &[|_| {}, |data| data.triggers = vec![TriggerIcon::Soul]],
+        );
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        assert_eq!(rules.active_player().hand.content, vec![cards[1]]);
+        assert_eq!(rules.active_player().waiting_room.content, vec![cards[0]]);
+    }
+
+    #[test]
+    fn standby_trigger() {
+        let mut rules = rules_with_trigger(TriggerIcon::Standby);
+        let attacker = rules
+            .active_player()
+            .stage
+            .get(StagePosition::CenterLeft)
+            .unwrap()
+            .card;
+        let cards = waiting_room(&mut rules, &[|data| data.base_stats.level = 2, |_| {}]);
+
+        rules.trigger_step(&mut (), &mut attack()).unwrap();
+
+        let character = rules
+            .active_player()
+            .stage
+            .get(StagePosition::CenterLeft)
+            .unwrap();
+        assert_eq!(character.card, cards[1]);
+        assert_eq!(
+            character.status,
+            crate::state::stage_state::CharacterStatus::Rested
+        );
+        assert_eq!(
+            rules.active_player().waiting_room.content,
+            vec![cards[0], attacker]
+        );
+    }
+}
@@ -1,5 +1,20 @@
-use crate::data::Phase;
+use crate::data::deck_list::DeckList;
+use crate::data::{CardDataId, CardId, Phase};
 use crate::state::player_state::PlayerState;
+use crate::state::rng::Rng;
+
+/// The seed used by games that aren't given one, so that they
+/// play out the same way every time.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// A physical card in a game.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CardInstance {
+    /// The player who brought the card to the game.
+    pub owner: usize,
+    /// The definition of the card in the game data.
+    pub data: CardDataId,
+}
 
 /// Holds all of the game state to be used by the rules
 /// manager.  Contains state information in regards to
@@ -7,30 +22,85 @@ use crate::state::player_state::PlayerState;
 #[derive(Debug)]
 pub struct GameState {
     pub players: [PlayerState; 2],
+    pub cards: Vec<CardInstance>,
     pub active_player: usize,
+    pub first_player: usize,
     pub phase: Phase,
     pub turn: usize,
+    pub rng: Rng,
 }
 
 impl GameState {
-    /// Creates a default GameState with 50 cards in each players deck.
+    /// Creates a default GameState with 50 cards in each players deck,
+    /// using the [`DEFAULT_SEED`].
     pub fn new() -> Self {
-        let mut players = [PlayerState::new(), PlayerState::new()];
+        Self::with_seed(DEFAULT_SEED)
+    }
+
+    /// Creates a default GameState with 50 cards in each players deck.
+    /// Both decks are made of one copy each of cards 0 to 49 in the
+    /// game data.  Every random choice in the game is decided by the `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        let mut state = Self::empty(seed);
+
+        for player in 0..state.players.len() {
+            for data in 0..50 {
+                let card = state.add_card(player, data.into());
+                state.players[player].deck.put_on_top(card);
+            }
+        }
 
-        for id in 0..50 {
-            for player in players.iter_mut() {
-                player.deck.put_on_top(id.into());
+        state
+    }
+
+    /// Creates a GameState where each player's deck is made from
+    /// their deck list in `decks`.  Every random choice in the game is
+    /// decided by the `seed`.
+    ///
+    /// Each card in a deck is a new physical card owned by that player,
+    /// and the decks are in the order of the deck lists, before shuffling.
+    pub fn with_decks(decks: [&DeckList; 2], seed: u64) -> Self {
+        let mut state = Self::empty(seed);
+
+        for (player, deck) in decks.iter().enumerate() {
+            for data in deck.copies() {
+                let card = state.add_card(player, data);
+                state.players[player].deck.put_on_top(card);
             }
         }
 
+        state
+    }
+
+    /// Creates a GameState without any cards.
+    fn empty(seed: u64) -> Self {
         Self {
-            players,
+            players: [PlayerState::new(), PlayerState::new()],
+            cards: Vec::new(),
             active_player: 0,
+            first_player: 0,
             phase: Phase::Stand,
             turn: 0,
+            rng: Rng::new(seed),
         }
     }
 
+    /// Creates a new physical card owned by the `owner`, which is a copy of the
+    /// `data` definition.  The card isn't put in any zone.
+    ///
+    /// Returns the id of the new card, which is unique in this game.
+    pub fn add_card(&mut self, owner: usize, data: CardDataId) -> CardId {
+        self.cards.push(CardInstance { owner, data });
+        CardId(self.cards.len() - 1)
+    }
+
+    /// Returns the instance of the `card`.
+    ///
+    /// Precondition: `card` must exist in this GameState.
+    pub fn card(&self, card: CardId) -> &CardInstance {
+        &self.cards[card.0]
+    }
+
     /// Returns the id of the active player.
     pub fn active_player(&self) -> usize {
         self.active_player
@@ -44,3 +114,82 @@ impl GameState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_is_deterministic() {
+        let mut first = GameState::new();
+        let mut second = GameState::new();
+
+        first.players[0].deck.shuffle(&mut first.rng);
+        second.players[0].deck.shuffle(&mut second.rng);
+
+        assert_eq!(
+            first.players[0].deck.content,
+            second.players[0].deck.content
+        );
+    }
+
+    #[test]
+    fn default_cards_are_unique() {
+        let state = GameState::with_seed(0);
+
+        assert_eq!(state.cards.len(), 100);
+        for (owner, player) in state.players.iter().enumerate() {
+            for (index, card) in player.deck.content.iter().enumerate() {
+                assert_eq!(state.card(*card).owner, owner);
+                assert_eq!(state.card(*card).data, index.into());
+                assert!(state.players[1 - owner]
+                    .deck
+                    .content
+                    .iter()
+                    .all(|other| other != card));
+            }
+        }
+    }
+
+    #[test]
+    fn with_decks() {
+        let first = DeckList {
+            cards: vec![(0.into(), 2), (3.into(), 1)],
+        };
+        let second = DeckList {
+            cards: vec![(3.into(), 2)],
+        };
+        let state = GameState::with_decks([&first, &second], 0);
+
+        assert_eq!(state.cards.len(), 5);
+        assert_eq!(
+            state.players[0].deck.content,
+            vec![0.into(), 1.into(), 2.into()]
+        );
+        assert_eq!(state.players[1].deck.content, vec![3.into(), 4.into()]);
+        for card in state.players[1].deck.content.iter() {
+            assert_eq!(state.card(*card).owner, 1);
+            assert_eq!(state.card(*card).data, 3.into());
+        }
+        assert_eq!(state.card(2.into()).data, 3.into());
+    }
+
+    #[test]
+    fn add_card() {
+        let mut state = GameState::with_seed(0);
+
+        let first = state.add_card(1, 7.into());
+        let second = state.add_card(0, 7.into());
+
+        assert_ne!(first, second);
+        assert_eq!(
+            state.card(first),
+            &CardInstance {
+                owner: 1,
+                data: 7.into()
+            }
+        );
+        assert_eq!(state.card(second).owner, 0);
+        assert_eq!(state.card(second).data, 7.into());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::data::CardId;
+
+/// Which way a card in memory is facing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Facing {
+    FaceUp,
+    FaceDown,
+}
+
+/// A card in memory, along with which way it is facing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MemoryCard {
+    pub card: CardId,
+    pub facing: Facing,
+}
+
+/// Contains the cards in a player's memory.  Unlike other zones,
+/// cards in memory can be face up or face down.
+#[derive(Debug)]
+pub struct MemoryState {
+    pub content: Vec<MemoryCard>,
+}
+
+impl MemoryState {
+    /// Creates an empty memory.
+    pub fn new() -> Self {
+        Self {
+            content: Vec::new(),
+        }
+    }
+
+    /// Puts `card` into memory facing the `facing` direction.
+    pub fn put(&mut self, card: CardId, facing: Facing) {
+        self.content.push(MemoryCard { card, facing });
+    }
+
+    /// Attempts to take the `card` out of memory.
+    ///
+    /// Returns the card taken if successful.
+    pub fn take_card_id(&mut self, card: CardId) -> Option<MemoryCard> {
+        Some(
+            self.content
+                .remove(self.content.iter().position(|item| item.card == card)?),
+        )
+    }
+
+    /// Returns the number of cards in memory.
+    pub fn count(&self) -> usize {
+        self.content.len()
+    }
+
+    /// Returns the number of cards in memory facing the `facing` direction.
+    pub fn count_facing(&self, facing: Facing) -> usize {
+        self.content
+            .iter()
+            .filter(|item| item.facing == facing)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count() {
+        let mut memory = MemoryState::new();
+        memory.put(0.into(), Facing::FaceUp);
+        memory.put(1.into(), Facing::FaceDown);
+        memory.put(2.into(), Facing::FaceDown);
+
+        assert_eq!(memory.count(), 3);
+        assert_eq!(memory.count_facing(Facing::FaceUp), 1);
+        assert_eq!(memory.count_facing(Facing::FaceDown), 2);
+    }
+
+    #[test]
+    fn take_card_id() {
+        let mut memory = MemoryState::new();
+        memory.put(0.into(), Facing::FaceDown);
+
+        assert_eq!(
+            memory.take_card_id(0.into()),
+            Some(MemoryCard {
+                card: 0.into(),
+                facing: Facing::FaceDown
+            })
+        );
+        assert_eq!(memory.take_card_id(0.into()), None);
+        assert_eq!(memory.count(), 0);
+    }
+}
@@ -1,3 +1,6 @@
 pub mod game_state;
+pub mod memory_state;
 pub mod player_state;
+pub mod rng;
+pub mod stage_state;
 pub mod zone_state;
@@ -1,4 +1,7 @@
 use crate::data::CardId;
+use crate::state::memory_state::MemoryState;
+use crate::state::rng::Rng;
+use crate::state::stage_state::{StagePosition, StageState};
 use crate::state::zone_state::ZoneState;
 
 /// The max size a players clock can be before triggering a level-up interrupt process.
@@ -15,8 +18,23 @@ pub struct PlayerState {
     pub waiting_room: ZoneState,
     pub clock: ZoneState,
     pub level: ZoneState,
+    pub stage: StageState,
+    pub climax: ZoneState,
+    pub stock: ZoneState,
+    pub resolution: ZoneState,
+    pub memory: MemoryState,
     pub base_hand_limit: usize,
     pub refresh_point: usize,
+    pub pending_abilities: Vec<TriggeredAbility>,
+}
+
+/// A triggered ability waiting to be resolved at the next check timing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TriggeredAbility {
+    /// The card the ability belongs to.
+    pub source: CardId,
+    /// The index of the ability in the source card's abilities.
+    pub ability: usize,
 }
 
 /// ZST representing the deck being empty.
@@ -42,6 +60,25 @@ pub enum LevelUpError {
     InvalidCard,
 }
 
+/// The kinds of errors attempting to play
+/// a character can create.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PlayCharacterError {
+    InvalidCard,
+}
+
+/// The kinds of errors attempting to play
+/// a climax can create.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PlayClimaxError {
+    InvalidCard,
+    ClimaxZoneOccupied,
+}
+
+/// ZST representing the stock not having enough cards to pay a cost.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct NotEnoughStock;
+
 /// The resulting data from a level up.  Contains
 /// what cards were processed during the level up
 /// and where they went.
@@ -51,6 +88,15 @@ pub struct LevelUpResult {
     pub sent_to_waiting_room: Vec<CardId>,
 }
 
+/// The resulting data from taking damage.  Contains
+/// every card that was revealed, and whether the damage
+/// was cancelled.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DamageResult {
+    pub revealed: Vec<CardId>,
+    pub cancelled: bool,
+}
+
 impl PlayerState {
     /// A default empty PlayerState.
     pub fn new() -> Self {
@@ -66,6 +112,59 @@ impl PlayerState {
         Ok(card)
     }
 
+    /// Trys to reveal the top card of the deck as damage,
+    /// putting it into the resolution zone.
+    ///
+    /// Returns the CardId revealed, or an error if there
+    /// are no cards in deck.
+    pub fn reveal_damage(&mut self) -> Result<CardId, DeckEmpty> {
+        let card = self.deck.take_top().ok_or(DeckEmpty)?;
+        self.resolution.put_on_top(card);
+        Ok(card)
+    }
+
+    /// Moves the revealed damage `cards` from the resolution
+    /// zone to the clock, in the order they were revealed.
+    ///
+    /// Precondition: `cards` must all be in the resolution zone.
+    pub fn take_damage(&mut self, cards: &[CardId]) {
+        for card in cards.iter() {
+            let card = self.resolution.take_card_id(*card).unwrap();
+            self.clock.put_on_top(card);
+        }
+    }
+
+    /// Moves the revealed damage `cards` from the resolution
+    /// zone to the waiting room, in the order they were revealed.
+    ///
+    /// Precondition: `cards` must all be in the resolution zone.
+    pub fn cancel_damage(&mut self, cards: &[CardId]) {
+        for card in cards.iter() {
+            let card = self.resolution.take_card_id(*card).unwrap();
+            self.waiting_room.put_on_top(card);
+        }
+    }
+
+    /// Trys to pay `amount` from stock, putting the top
+    /// `amount` cards of the stock into the waiting room.
+    ///
+    /// Returns the cards paid, or an error if there
+    /// are not enough cards in stock.
+    pub fn pay_stock(&mut self, amount: usize) -> Result<Vec<CardId>, NotEnoughStock> {
+        if self.stock.content.len() < amount {
+            return Err(NotEnoughStock);
+        }
+
+        let paid: Vec<_> = (0..amount)
+            .map(|_| self.stock.take_top().unwrap())
+            .collect();
+        for card in paid.iter() {
+            self.waiting_room.put_on_top(*card);
+        }
+
+        Ok(paid)
+    }
+
     /// Trys to discard the `card` specified from hand.
     ///
     /// Returns the card discared (i.e. `card`) or an error
@@ -85,6 +184,107 @@ impl PlayerState {
         Ok(card)
     }
 
+    /// Takes the character in `position` off of the stage, and puts
+    /// its markers into the waiting room.
+    ///
+    /// Returns the card taken, if there was one.
+    pub fn remove_character(&mut self, position: StagePosition) -> Option<CardId> {
+        let character = self.stage.take_from_slot(position)?;
+        for marker in character.markers {
+            self.waiting_room.put_on_top(marker);
+        }
+        Some(character.card)
+    }
+
+    /// Moves the character in `position` onto the top of the waiting room,
+    /// followed by its markers.
+    ///
+    /// Returns the card moved, if there was one.
+    pub fn character_to_waiting_room(&mut self, position: StagePosition) -> Option<CardId> {
+        let character = self
+            .stage
+            .take_to_zone(position, &mut self.waiting_room)
+            .ok()?;
+        self.waiting_room.content.extend(character.markers);
+        Some(character.card)
+    }
+
+    /// Returns the character in `position` to the hand, and puts
+    /// its markers into the waiting room.
+    ///
+    /// Returns the card moved, if there was one.
+    pub fn character_to_hand(&mut self, position: StagePosition) -> Option<CardId> {
+        let character = self.stage.take_to_zone(position, &mut self.hand).ok()?;
+        self.waiting_room.content.extend(character.markers);
+        Some(character.card)
+    }
+
+    /// Returns the number of markers under every character on the stage.
+    pub fn total_marker_count(&self) -> usize {
+        self.stage
+            .characters()
+            .map(|(_, character)| character.markers.len())
+            .sum()
+    }
+
+    /// Trys to play the `card` specified from hand
+    /// into the `position` on stage.
+    ///
+    /// If there was already a character in that position
+    /// it is put into the waiting room, and returned.
+    pub fn play_character(
+        &mut self,
+        card: CardId,
+        position: StagePosition,
+    ) -> Result<Option<CardId>, PlayCharacterError> {
+        if !self.hand.content.contains(&card) {
+            return Err(PlayCharacterError::InvalidCard);
+        }
+
+        let replaced = self.character_to_waiting_room(position);
+        self.stage
+            .put_from_zone(position, &mut self.hand, card)
+            .unwrap();
+
+        Ok(replaced)
+    }
+
+    /// Trys to play the `card` specified from hand
+    /// into the climax zone.
+    ///
+    /// Returns the card played, or an error if the
+    /// climax zone already has a card in it.
+    pub fn play_climax(&mut self, card: CardId) -> Result<CardId, PlayClimaxError> {
+        if !self.climax.content.is_empty() {
+            return Err(PlayClimaxError::ClimaxZoneOccupied);
+        }
+
+        let card = self
+            .hand
+            .take_card_id(card)
+            .ok_or(PlayClimaxError::InvalidCard)?;
+
+        self.climax.put_on_top(card);
+
+        Ok(card)
+    }
+
+    /// Puts every card in the climax zone into the waiting room.
+    ///
+    /// Returns the cards that were moved.
+    pub fn clear_climax(&mut self) -> Vec<CardId> {
+        let cards: Vec<_> = self.climax.content.drain(..).collect();
+        for card in cards.iter() {
+            self.waiting_room.put_on_top(*card);
+        }
+        cards
+    }
+
+    /// Returns the player's current level.
+    pub fn current_level(&self) -> i32 {
+        self.level.content.len() as i32
+    }
+
     /// Checks to see if the player's current
     /// hand exceeds their handlimit.
     pub fn exceeding_handlimit(&self) -> bool {
@@ -132,16 +332,42 @@ impl PlayerState {
         })
     }
 
-    pub fn refresh(&mut self) -> Result<bool, DeckNotEmpty> {
+    /// Pays one refresh point by putting the top card of the deck
+    /// into the clock.
+    ///
+    /// Returns the card put into the clock, or None if there
+    /// are no refresh points to pay or the deck is empty.
+    pub fn pay_refresh_point(&mut self) -> Option<CardId> {
+        if self.refresh_point == 0 {
+            return None;
+        }
+
+        let card = self.deck.take_top()?;
+        self.clock.put_on_top(card);
+        self.refresh_point -= 1;
+
+        Some(card)
+    }
+
+    /// Attempts to refresh, shuffling the waiting room into a new deck.
+    ///
+    /// Returns whether the player lost the game because their
+    /// waiting room was also empty, in which case nothing happens,
+    /// or an error if the deck is not empty.
+    pub fn refresh(&mut self, rng: &mut Rng) -> Result<bool, DeckNotEmpty> {
         if !self.needs_to_refresh() {
             return Err(DeckNotEmpty);
         }
 
+        if self.waiting_room.content.is_empty() {
+            return Ok(true);
+        }
+
         self.deck.content = self.waiting_room.content.drain(..).collect();
 
         self.refresh_point += 1;
 
-        self.deck.shuffle();
+        self.deck.shuffle(rng);
 
         Ok(false)
     }
@@ -155,8 +381,14 @@ impl Default for PlayerState {
             waiting_room: ZoneState::new(),
             clock: ZoneState::new(),
             level: ZoneState::new(),
+            stage: StageState::new(),
+            climax: ZoneState::new(),
+            stock: ZoneState::new(),
+            resolution: ZoneState::new(),
+            memory: MemoryState::new(),
             base_hand_limit: 7,
             refresh_point: 0,
+            pending_abilities: Vec::new(),
         }
     }
 }
@@ -277,6 +509,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn play_character() {
+        let mut player = PlayerState {
+            hand: ZoneState::with_content(vec![0.into(), 1.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            player.play_character(0.into(), StagePosition::CenterLeft),
+            Ok(None)
+        );
+        assert_eq!(player.hand.content, vec![1.into()]);
+        assert_eq!(
+            player.stage.get(StagePosition::CenterLeft).unwrap().card,
+            0.into()
+        );
+
+        assert_eq!(
+            player.play_character(1.into(), StagePosition::CenterLeft),
+            Ok(Some(0.into()))
+        );
+        assert_eq!(player.waiting_room.content, vec![0.into()]);
+        assert_eq!(
+            player.stage.get(StagePosition::CenterLeft).unwrap().card,
+            1.into()
+        );
+
+        assert_eq!(
+            player.play_character(1.into(), StagePosition::CenterRight),
+            Err(PlayCharacterError::InvalidCard)
+        );
+    }
+
+    #[test]
+    fn play_climax() {
+        let mut player = PlayerState {
+            hand: ZoneState::with_content(vec![0.into(), 1.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            player.play_climax(2.into()),
+            Err(PlayClimaxError::InvalidCard)
+        );
+        assert_eq!(player.play_climax(0.into()), Ok(0.into()));
+        assert_eq!(player.climax.content, vec![0.into()]);
+        assert_eq!(player.hand.content, vec![1.into()]);
+
+        assert_eq!(
+            player.play_climax(1.into()),
+            Err(PlayClimaxError::ClimaxZoneOccupied)
+        );
+        assert_eq!(player.hand.content, vec![1.into()]);
+    }
+
+    #[test]
+    fn clear_climax() {
+        let mut player = PlayerState {
+            climax: ZoneState::with_content(vec![0.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(player.clear_climax(), vec![0.into()]);
+        assert!(player.climax.content.is_empty());
+        assert_eq!(player.waiting_room.content, vec![0.into()]);
+
+        assert_eq!(player.clear_climax(), vec![]);
+    }
+
+    #[test]
+    fn take_damage() {
+        let mut player = PlayerState {
+            deck: ZoneState::with_content(vec![0.into(), 1.into(), 2.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(player.reveal_damage(), Ok(2.into()));
+        assert_eq!(player.reveal_damage(), Ok(1.into()));
+        assert_eq!(player.resolution.content, vec![2.into(), 1.into()]);
+
+        player.take_damage(&[2.into(), 1.into()]);
+
+        assert!(player.resolution.content.is_empty());
+        assert_eq!(player.clock.content, vec![2.into(), 1.into()]);
+        assert_eq!(player.deck.content, vec![0.into()]);
+    }
+
+    #[test]
+    fn cancel_damage() {
+        let mut player = PlayerState {
+            deck: ZoneState::with_content(vec![0.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(player.reveal_damage(), Ok(0.into()));
+        assert_eq!(player.reveal_damage(), Err(DeckEmpty));
+
+        player.cancel_damage(&[0.into()]);
+
+        assert!(player.resolution.content.is_empty());
+        assert!(player.clock.content.is_empty());
+        assert_eq!(player.waiting_room.content, vec![0.into()]);
+    }
+
+    #[test]
+    fn pay_stock() {
+        let mut player = PlayerState {
+            stock: ZoneState::with_content(vec![0.into(), 1.into(), 2.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(player.pay_stock(4), Err(NotEnoughStock));
+        assert_eq!(player.stock.content.len(), 3);
+
+        assert_eq!(player.pay_stock(2), Ok(vec![2.into(), 1.into()]));
+        assert_eq!(player.stock.content, vec![0.into()]);
+        assert_eq!(player.waiting_room.content, vec![2.into(), 1.into()]);
+    }
+
     #[test]
     fn refresh() {
         let mut player = PlayerState {
@@ -288,11 +639,99 @@ mod tests {
 
         assert!(player.needs_to_refresh());
 
-        assert_eq!(player.refresh(), Ok(false));
+        assert_eq!(player.refresh(&mut Rng::new(0)), Ok(false));
 
         assert_eq!(player.deck.content.len(), 1);
         assert_eq!(player.waiting_room.content.len(), 0);
         assert_eq!(player.deck.content, vec![0.into()]);
         assert_eq!(player.refresh_point, starting_refresh_point + 1);
     }
+
+    #[test]
+    fn pay_refresh_point() {
+        let mut player = PlayerState {
+            deck: ZoneState::with_content(vec![0.into(), 1.into()]),
+            ..Default::default()
+        };
+
+        assert_eq!(player.pay_refresh_point(), None);
+
+        player.refresh_point = 1;
+        assert_eq!(player.pay_refresh_point(), Some(1.into()));
+        assert_eq!(player.clock.content, vec![1.into()]);
+        assert_eq!(player.deck.content, vec![0.into()]);
+        assert_eq!(player.refresh_point, 0);
+    }
+
+    #[test]
+    fn remove_character() {
+        let mut player = PlayerState::default();
+        player
+            .stage
+            .put_in_slot(StagePosition::CenterLeft, 0.into())
+            .unwrap();
+        player
+            .stage
+            .put_in_slot(StagePosition::BackLeft, 3.into())
+            .unwrap();
+        player
+            .stage
+            .put_marker(StagePosition::CenterLeft, 1.into())
+            .unwrap();
+        player
+            .stage
+            .put_marker(StagePosition::CenterLeft, 2.into())
+            .unwrap();
+        player
+            .stage
+            .put_marker(StagePosition::BackLeft, 4.into())
+            .unwrap();
+
+        assert_eq!(player.total_marker_count(), 3);
+
+        assert_eq!(
+            player.remove_character(StagePosition::CenterLeft),
+            Some(0.into())
+        );
+        assert_eq!(player.waiting_room.content, vec![1.into(), 2.into()]);
+        assert_eq!(player.total_marker_count(), 1);
+        assert_eq!(player.remove_character(StagePosition::CenterLeft), None);
+    }
+
+    #[test]
+    fn character_to_zone() {
+        let mut player = PlayerState::default();
+        player
+            .stage
+            .put_in_slot(StagePosition::CenterLeft, 0.into())
+            .unwrap();
+        player
+            .stage
+            .put_in_slot(StagePosition::BackLeft, 1.into())
+            .unwrap();
+        player
+            .stage
+            .put_marker(StagePosition::BackLeft, 2.into())
+            .unwrap();
+
+        assert_eq!(
+            player.character_to_waiting_room(StagePosition::CenterLeft),
+            Some(0.into())
+        );
+        assert_eq!(
+            player.character_to_hand(StagePosition::BackLeft),
+            Some(1.into())
+        );
+        assert_eq!(player.waiting_room.content, vec![0.into(), 2.into()]);
+        assert_eq!(player.hand.content, vec![1.into()]);
+        assert_eq!(player.character_to_hand(StagePosition::BackLeft), None);
+    }
+
+    #[test]
+    fn refresh_empty_waiting_room() {
+        let mut player = PlayerState::default();
+
+        assert_eq!(player.refresh(&mut Rng::new(0)), Ok(true));
+        assert_eq!(player.refresh_point, 0);
+    }
 }
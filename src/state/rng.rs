@@ -0,0 +1,84 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+const INCREMENT: u64 = 1_442_695_040_888_963_407;
+
+/// A small PCG32 random number generator.  For a given
+/// seed, it will always produce the same sequence of numbers,
+/// so games can be reproduced.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from the `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self { state: 0 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    /// Returns a seed that is different every time it is called.
+    pub fn random_seed() -> u64 {
+        RandomState::new().build_hasher().finish()
+    }
+
+    /// Returns the next random number.
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT);
+
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rotation = (old >> 59) as u32;
+        xorshifted.rotate_right(rotation)
+    }
+
+    /// Returns a random number in the range `0..bound`, without bias.
+    ///
+    /// Precondition: `bound` is not 0.
+    pub fn below(&mut self, bound: usize) -> usize {
+        let bound = bound as u32;
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let value = self.next_u32();
+            if value >= threshold {
+                return (value % bound) as usize;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let mut first = Rng::new(42);
+        let mut second = Rng::new(42);
+        let mut other = Rng::new(43);
+
+        let first: Vec<_> = (0..8).map(|_| first.next_u32()).collect();
+        let second: Vec<_> = (0..8).map(|_| second.next_u32()).collect();
+        let other: Vec<_> = (0..8).map(|_| other.next_u32()).collect();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn below() {
+        let mut rng = Rng::new(0);
+
+        for bound in 1..20 {
+            for _ in 0..20 {
+                assert!(rng.below(bound) < bound);
+            }
+        }
+        assert_eq!(rng.below(1), 0);
+    }
+}
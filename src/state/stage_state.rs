@@ -0,0 +1,534 @@
+use crate::data::CardId;
+use crate::state::zone_state::ZoneState;
+
+/// The five positions a character can occupy on a player's stage.
+///
+/// Center positions are listed left to right from the owning
+/// player's point of view.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StagePosition {
+    CenterLeft,
+    CenterMiddle,
+    CenterRight,
+    BackLeft,
+    BackRight,
+}
+
+impl StagePosition {
+    /// Every stage position, center stage first.
+    pub const ALL: [StagePosition; 5] = [
+        StagePosition::CenterLeft,
+        StagePosition::CenterMiddle,
+        StagePosition::CenterRight,
+        StagePosition::BackLeft,
+        StagePosition::BackRight,
+    ];
+
+    /// The three center stage positions.
+    pub const CENTER: [StagePosition; 3] = [
+        StagePosition::CenterLeft,
+        StagePosition::CenterMiddle,
+        StagePosition::CenterRight,
+    ];
+
+    /// The two back stage positions.
+    pub const BACK: [StagePosition; 2] = [StagePosition::BackLeft, StagePosition::BackRight];
+
+    /// Checks to see if this position is on the center stage.
+    pub fn is_center(self) -> bool {
+        match self {
+            StagePosition::CenterLeft
+            | StagePosition::CenterMiddle
+            | StagePosition::CenterRight => true,
+            StagePosition::BackLeft | StagePosition::BackRight => false,
+        }
+    }
+
+    /// Returns the position on the opponent's stage that is
+    /// in front of this position.  Since the stages face each
+    /// other, left and right are mirrored.
+    ///
+    /// Back stage positions have nothing in front of them.
+    pub fn opposing(self) -> Option<StagePosition> {
+        match self {
+            StagePosition::CenterLeft => Some(StagePosition::CenterRight),
+            StagePosition::CenterMiddle => Some(StagePosition::CenterMiddle),
+            StagePosition::CenterRight => Some(StagePosition::CenterLeft),
+            StagePosition::BackLeft | StagePosition::BackRight => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            StagePosition::CenterLeft => 0,
+            StagePosition::CenterMiddle => 1,
+            StagePosition::CenterRight => 2,
+            StagePosition::BackLeft => 3,
+            StagePosition::BackRight => 4,
+        }
+    }
+}
+
+/// The orientation of a character on the stage.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CharacterStatus {
+    Standing,
+    Rested,
+    Reversed,
+}
+
+/// Changes to a character's stats that last until the end of the turn.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct TurnModifiers {
+    pub power: i32,
+    pub soul: i32,
+}
+
+/// A character that is currently on the stage.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StageCharacter {
+    pub card: CardId,
+    pub status: CharacterStatus,
+    pub turn_modifiers: TurnModifiers,
+    /// The cards put under this character as markers, bottom first.
+    pub markers: Vec<CardId>,
+}
+
+/// The kinds of errors attempting to change the
+/// stage can create.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StageError {
+    SlotOccupied,
+    SlotEmpty,
+    CardNotInZone,
+}
+
+/// Contains the five stage slots of a player.  Each slot
+/// holds at most one character.
+#[derive(Debug)]
+pub struct StageState {
+    pub slots: [Option<StageCharacter>; 5],
+}
+
+impl StageState {
+    /// Creates an empty stage.
+    pub fn new() -> Self {
+        Self {
+            slots: [None, None, None, None, None],
+        }
+    }
+
+    /// Returns the character in the `position`, if there is one.
+    pub fn get(&self, position: StagePosition) -> Option<&StageCharacter> {
+        self.slots[position.index()].as_ref()
+    }
+
+    /// Returns a mutable reference to the character in the `position`, if there is one.
+    pub fn get_mut(&mut self, position: StagePosition) -> Option<&mut StageCharacter> {
+        self.slots[position.index()].as_mut()
+    }
+
+    /// Checks to see if the `position` has no character in it.
+    pub fn is_empty(&self, position: StagePosition) -> bool {
+        self.get(position).is_none()
+    }
+
+    /// Returns every occupied position, along with the character in it.
+    pub fn characters(&self) -> impl Iterator<Item = (StagePosition, &StageCharacter)> {
+        StagePosition::ALL
+            .iter()
+            .filter_map(move |position| Some((*position, self.get(*position)?)))
+    }
+
+    /// Returns the position the `card` is in, if it is on the stage.
+    pub fn position_of(&self, card: CardId) -> Option<StagePosition> {
+        self.characters()
+            .find(|(_, character)| character.card == card)
+            .map(|(position, _)| position)
+    }
+
+    /// Attempts to put `card` into the `position` as a standing character.
+    ///
+    /// Returns an error if the `position` is already occupied.
+    pub fn put_in_slot(&mut self, position: StagePosition, card: CardId) -> Result<(), StageError> {
+        let slot = &mut self.slots[position.index()];
+        if slot.is_some() {
+            return Err(StageError::SlotOccupied);
+        }
+        *slot = Some(StageCharacter {
+            card,
+            status: CharacterStatus::Standing,
+            turn_modifiers: TurnModifiers::default(),
+            markers: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Attempts to take the character out of the `position`.
+    ///
+    /// Returns the character taken if successful, along with its markers.
+    pub fn take_from_slot(&mut self, position: StagePosition) -> Option<StageCharacter> {
+        self.slots[position.index()].take()
+    }
+
+    /// Attempts to move `card` out of `zone` into the `position`
+    /// as a standing character.
+    ///
+    /// Returns an error if the `position` is already occupied,
+    /// or the `card` isn't in the `zone`.
+    pub fn put_from_zone(
+        &mut self,
+        position: StagePosition,
+        zone: &mut ZoneState,
+        card: CardId,
+    ) -> Result<(), StageError> {
+        if !self.is_empty(position) {
+            return Err(StageError::SlotOccupied);
+        }
+        let card = zone.take_card_id(card).ok_or(StageError::CardNotInZone)?;
+        self.put_in_slot(position, card)
+    }
+
+    /// Attempts to move the character in `position` onto the top of `zone`.
+    ///
+    /// Returns the character moved if successful.  Its markers are
+    /// not moved, and are left for the caller to put somewhere.
+    pub fn take_to_zone(
+        &mut self,
+        position: StagePosition,
+        zone: &mut ZoneState,
+    ) -> Result<StageCharacter, StageError> {
+        let character = self.take_from_slot(position).ok_or(StageError::SlotEmpty)?;
+        zone.put_on_top(character.card);
+        Ok(character)
+    }
+
+    /// Attempts to put `card` under the character in `position` as a marker.
+    pub fn put_marker(&mut self, position: StagePosition, card: CardId) -> Result<(), StageError> {
+        let character = self.get_mut(position).ok_or(StageError::SlotEmpty)?;
+        character.markers.push(card);
+        Ok(())
+    }
+
+    /// Returns the number of markers under the character in `position`,
+    /// or 0 if there is no character.
+    pub fn marker_count(&self, position: StagePosition) -> usize {
+        self.get(position)
+            .map_or(0, |character| character.markers.len())
+    }
+
+    /// Attempts to move the character in `from` to the empty position `to`.
+    pub fn move_to(&mut self, from: StagePosition, to: StagePosition) -> Result<(), StageError> {
+        if self.is_empty(from) {
+            return Err(StageError::SlotEmpty);
+        }
+        if !self.is_empty(to) {
+            return Err(StageError::SlotOccupied);
+        }
+        self.slots.swap(from.index(), to.index());
+        Ok(())
+    }
+
+    /// Swaps the contents of two positions.  Either position may
+    /// be empty, but not both.
+    pub fn swap(&mut self, lhs: StagePosition, rhs: StagePosition) -> Result<(), StageError> {
+        if self.is_empty(lhs) && self.is_empty(rhs) {
+            return Err(StageError::SlotEmpty);
+        }
+        self.slots.swap(lhs.index(), rhs.index());
+        Ok(())
+    }
+
+    /// Attempts to change the status of the character in `position`.
+    pub fn set_status(
+        &mut self,
+        position: StagePosition,
+        status: CharacterStatus,
+    ) -> Result<(), StageError> {
+        let character = self.get_mut(position).ok_or(StageError::SlotEmpty)?;
+        character.status = status;
+        Ok(())
+    }
+
+    /// Attempts to stand the character in `position`.
+    pub fn stand(&mut self, position: StagePosition) -> Result<(), StageError> {
+        self.set_status(position, CharacterStatus::Standing)
+    }
+
+    /// Attempts to rest the character in `position`.
+    pub fn rest(&mut self, position: StagePosition) -> Result<(), StageError> {
+        self.set_status(position, CharacterStatus::Rested)
+    }
+
+    /// Attempts to reverse the character in `position`.
+    pub fn reverse(&mut self, position: StagePosition) -> Result<(), StageError> {
+        self.set_status(position, CharacterStatus::Reversed)
+    }
+
+    /// Removes the turn modifiers from every character on the stage.
+    pub fn clear_turn_modifiers(&mut self) {
+        for character in self.slots.iter_mut().flatten() {
+            character.turn_modifiers = TurnModifiers::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_from_zone() {
+        let mut stage = StageState::new();
+        let mut zone = ZoneState::with_content(vec![0.into(), 1.into()]);
+
+        assert_eq!(
+            stage.put_from_zone(StagePosition::BackLeft, &mut zone, 1.into()),
+            Ok(())
+        );
+        assert_eq!(zone.content, vec![0.into()]);
+        assert_eq!(
+            stage
+                .get(StagePosition::BackLeft)
+                .map(|character| character.card),
+            Some(1.into())
+        );
+
+        assert_eq!(
+            stage.put_from_zone(StagePosition::BackLeft, &mut zone, 0.into()),
+            Err(StageError::SlotOccupied)
+        );
+        assert_eq!(
+            stage.put_from_zone(StagePosition::BackRight, &mut zone, 1.into()),
+            Err(StageError::CardNotInZone)
+        );
+        assert_eq!(zone.content, vec![0.into()]);
+    }
+
+    #[test]
+    fn take_to_zone() {
+        let mut stage = StageState::new();
+        let mut zone = ZoneState::with_content(vec![0.into()]);
+        stage
+            .put_in_slot(StagePosition::CenterLeft, 1.into())
+            .unwrap();
+        stage
+            .put_marker(StagePosition::CenterLeft, 2.into())
+            .unwrap();
+
+        let character = stage
+            .take_to_zone(StagePosition::CenterLeft, &mut zone)
+            .unwrap();
+
+        assert_eq!(character.card, 1.into());
+        assert_eq!(character.markers, vec![2.into()]);
+        assert_eq!(zone.content, vec![0.into(), 1.into()]);
+        assert!(stage.is_empty(StagePosition::CenterLeft));
+        assert_eq!(
+            stage.take_to_zone(StagePosition::CenterLeft, &mut zone),
+            Err(StageError::SlotEmpty)
+        );
+    }
+
+    #[test]
+    fn put_in_slot() {
+        let mut stage = StageState::new();
+
+        assert_eq!(
+            stage.put_in_slot(StagePosition::CenterLeft, 0.into()),
+            Ok(())
+        );
+        assert_eq!(
+            stage.get(StagePosition::CenterLeft),
+            Some(&StageCharacter {
+                card: 0.into(),
+                status: CharacterStatus::Standing,
+                turn_modifiers: TurnModifiers::default(),
+                markers: Vec::new(),
+            })
+        );
+
+        assert_eq!(
+            stage.put_in_slot(StagePosition::CenterLeft, 1.into()),
+            Err(StageError::SlotOccupied)
+        );
+        assert_eq!(stage.get(StagePosition::CenterLeft).unwrap().card, 0.into());
+    }
+
+    #[test]
+    fn markers() {
+        let mut stage = StageState::new();
+
+        assert_eq!(
+            stage.put_marker(StagePosition::CenterLeft, 1.into()),
+            Err(StageError::SlotEmpty)
+        );
+
+        stage
+            .put_in_slot(StagePosition::CenterLeft, 0.into())
+            .unwrap();
+        stage
+            .put_marker(StagePosition::CenterLeft, 1.into())
+            .unwrap();
+        stage
+            .put_marker(StagePosition::CenterLeft, 2.into())
+            .unwrap();
+
+        assert_eq!(stage.marker_count(StagePosition::CenterLeft), 2);
+        assert_eq!(stage.marker_count(StagePosition::CenterRight), 0);
+        assert_eq!(
+            stage
+                .take_from_slot(StagePosition::CenterLeft)
+                .unwrap()
+                .markers,
+            vec![1.into(), 2.into()]
+        );
+    }
+
+    #[test]
+    fn take_from_slot() {
+        let mut stage = StageState::new();
+        stage
+            .put_in_slot(StagePosition::BackRight, 0.into())
+            .unwrap();
+
+        assert_eq!(
+            stage
+                .take_from_slot(StagePosition::BackRight)
+                .map(|character| character.card),
+            Some(0.into())
+        );
+        assert_eq!(stage.take_from_slot(StagePosition::BackRight), None);
+        assert!(stage.is_empty(StagePosition::BackRight));
+    }
+
+    #[test]
+    fn move_to() {
+        let mut stage = StageState::new();
+        stage
+            .put_in_slot(StagePosition::CenterLeft, 0.into())
+            .unwrap();
+        stage
+            .put_in_slot(StagePosition::CenterRight, 1.into())
+            .unwrap();
+
+        assert_eq!(
+            stage.move_to(StagePosition::CenterLeft, StagePosition::CenterRight),
+            Err(StageError::SlotOccupied)
+        );
+        assert_eq!(
+            stage.move_to(StagePosition::BackLeft, StagePosition::BackRight),
+            Err(StageError::SlotEmpty)
+        );
+
+        stage.rest(StagePosition::CenterLeft).unwrap();
+        assert_eq!(
+            stage.move_to(StagePosition::CenterLeft, StagePosition::BackLeft),
+            Ok(())
+        );
+        assert!(stage.is_empty(StagePosition::CenterLeft));
+        assert_eq!(
+            stage.get(StagePosition::BackLeft),
+            Some(&StageCharacter {
+                card: 0.into(),
+                status: CharacterStatus::Rested,
+                turn_modifiers: TurnModifiers::default(),
+                markers: Vec::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn swap() {
+        let mut stage = StageState::new();
+        stage
+            .put_in_slot(StagePosition::CenterLeft, 0.into())
+            .unwrap();
+        stage
+            .put_in_slot(StagePosition::CenterRight, 1.into())
+            .unwrap();
+
+        assert_eq!(
+            stage.swap(StagePosition::CenterLeft, StagePosition::CenterRight),
+            Ok(())
+        );
+        assert_eq!(
+            stage.position_of(0.into()),
+            Some(StagePosition::CenterRight)
+        );
+        assert_eq!(stage.position_of(1.into()), Some(StagePosition::CenterLeft));
+
+        assert_eq!(
+            stage.swap(StagePosition::BackLeft, StagePosition::BackRight),
+            Err(StageError::SlotEmpty)
+        );
+    }
+
+    #[test]
+    fn change_status() {
+        let mut stage = StageState::new();
+        stage
+            .put_in_slot(StagePosition::CenterMiddle, 0.into())
+            .unwrap();
+
+        stage.rest(StagePosition::CenterMiddle).unwrap();
+        assert_eq!(
+            stage.get(StagePosition::CenterMiddle).unwrap().status,
+            CharacterStatus::Rested
+        );
+
+        stage.reverse(StagePosition::CenterMiddle).unwrap();
+        assert_eq!(
+            stage.get(StagePosition::CenterMiddle).unwrap().status,
+            CharacterStatus::Reversed
+        );
+
+        stage.stand(StagePosition::CenterMiddle).unwrap();
+        assert_eq!(
+            stage.get(StagePosition::CenterMiddle).unwrap().status,
+            CharacterStatus::Standing
+        );
+
+        assert_eq!(
+            stage.rest(StagePosition::BackLeft),
+            Err(StageError::SlotEmpty)
+        );
+    }
+
+    #[test]
+    fn clear_turn_modifiers() {
+        let mut stage = StageState::new();
+        stage
+            .put_in_slot(StagePosition::CenterMiddle, 0.into())
+            .unwrap();
+
+        stage
+            .get_mut(StagePosition::CenterMiddle)
+            .unwrap()
+            .turn_modifiers
+            .soul += 1;
+
+        stage.clear_turn_modifiers();
+
+        assert_eq!(
+            stage
+                .get(StagePosition::CenterMiddle)
+                .unwrap()
+                .turn_modifiers,
+            TurnModifiers::default()
+        );
+    }
+
+    #[test]
+    fn opposing() {
+        assert_eq!(
+            StagePosition::CenterLeft.opposing(),
+            Some(StagePosition::CenterRight)
+        );
+        assert_eq!(
+            StagePosition::CenterMiddle.opposing(),
+            Some(StagePosition::CenterMiddle)
+        );
+        assert_eq!(StagePosition::BackLeft.opposing(), None);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::data::CardId;
+use crate::state::rng::Rng;
+
 #[derive(Debug)]
 pub struct ZoneState {
     pub content: Vec<CardId>,
@@ -44,8 +46,12 @@ impl ZoneState {
         )
     }
 
-    pub fn shuffle(&mut self) {
-        // TODO: actually shuffle cards
+    /// Shuffles the zone using `rng`.
+    pub fn shuffle(&mut self, rng: &mut Rng) {
+        for i in (1..self.content.len()).rev() {
+            let j = rng.below(i + 1);
+            self.content.swap(i, j);
+        }
     }
 }
 
@@ -85,4 +91,19 @@ mod tests {
         assert_eq!(zone.take_card_id(3.into()), Some(3.into()));
         assert_eq!(zone.content, vec![0.into(), 2.into(), 4.into()]);
     }
+    #[test]
+    fn shuffle() {
+        let content: Vec<CardId> = (0..50).map(CardId::from).collect();
+        let mut zone = ZoneState::with_content(content.clone());
+        let mut other = ZoneState::with_content(content.clone());
+
+        zone.shuffle(&mut Rng::new(7));
+        other.shuffle(&mut Rng::new(7));
+
+        assert_eq!(zone.content, other.content);
+        assert_ne!(zone.content, content);
+
+        zone.content.sort_by_key(|card| card.0);
+        assert_eq!(zone.content, content);
+    }
 }
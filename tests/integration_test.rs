@@ -1,6 +1,13 @@
+use ws_engine::data::card_data::TriggerIcon;
+use ws_engine::data::card_database::SAMPLE_CARDS;
+use ws_engine::data::cost::Cost;
+use ws_engine::data::deck_list::{DeckList, SAMPLE_DECK};
+use ws_engine::data::game_data::GameData;
 use ws_engine::data::{CardId, Phase};
-use ws_engine::rules::io::{ChoiceContext, Input, Output};
-use ws_engine::state::player_state::LevelUpResult;
+use ws_engine::rules::io::{AttackType, ChoiceContext, Input, Output, RulesEngineIO};
+use ws_engine::rules::{GameResult, LossReason, Rules};
+use ws_engine::state::player_state::{DamageResult, LevelUpResult, TriggeredAbility};
+use ws_engine::state::stage_state::StagePosition;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Event {
@@ -24,76 +31,345 @@ enum Event {
         result: LevelUpResult,
         player: usize,
     },
+    Shuffle {
+        player: usize,
+    },
+    FirstPlayer {
+        player: usize,
+    },
+    Mulligan {
+        cards: Vec<CardId>,
+        player: usize,
+    },
     Refreshed {
         player: usize,
     },
+    RefreshDamage {
+        card: CardId,
+        player: usize,
+    },
+    ResolveAbility {
+        ability: TriggeredAbility,
+        player: usize,
+    },
+    MoveCharacter {
+        from: StagePosition,
+        to: StagePosition,
+        player: usize,
+    },
+    PlayEventCard {
+        card: CardId,
+        player: usize,
+    },
+    ResolvedEventCard {
+        card: CardId,
+        player: usize,
+    },
+    PlayCharacter {
+        card: CardId,
+        position: StagePosition,
+        player: usize,
+    },
+    PlayClimax {
+        card: CardId,
+        player: usize,
+    },
+    DeclareAttack {
+        attacker: StagePosition,
+        attack_type: AttackType,
+        player: usize,
+    },
+    Trigger {
+        card: CardId,
+        player: usize,
+    },
+    TriggerEffect {
+        icon: TriggerIcon,
+        card: Option<CardId>,
+        player: usize,
+    },
+    DamageRevealed {
+        card: CardId,
+        player: usize,
+    },
+    Damage {
+        result: DamageResult,
+        player: usize,
+    },
+    Stand {
+        position: StagePosition,
+        player: usize,
+    },
+    Counter {
+        card: CardId,
+        position: StagePosition,
+        player: usize,
+    },
+    Reverse {
+        position: StagePosition,
+        player: usize,
+    },
+    PayCost {
+        cost: Cost,
+        cards: Vec<CardId>,
+        player: usize,
+    },
+    Encore {
+        card: CardId,
+        position: StagePosition,
+        cost: Option<Vec<Cost>>,
+        player: usize,
+    },
 }
 
-struct MayAnswer {
-    options: Vec<CardId>,
-    player: usize,
+/// A choice a player was asked to make, and the option they picked.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Choice {
     context: ChoiceContext,
-    value: Option<usize>,
-}
-struct MustAnswer {
-    options: Vec<CardId>,
     player: usize,
-    context: ChoiceContext,
-    value: usize,
+    /// How many options the player was given.
+    options: usize,
+    answer: Option<usize>,
 }
 
+/// Logs every event and choice in a game, so a test can check them
+/// once the game is over.  Players always pick the first option.
+#[derive(Default)]
 struct IntegrationIO {
     events: Vec<Event>,
-    may_answers: Vec<MayAnswer>,
-    must_answers: Vec<MustAnswer>,
+    choices: Vec<Choice>,
+}
+
+impl IntegrationIO {
+    /// Returns how many times `player` leveled up.
+    fn level_ups(&self, player: usize) -> usize {
+        self.events
+            .iter()
+            .filter(|event| matches!(event, Event::LevelUp { player: p, .. } if *p == player))
+            .count()
+    }
 }
 
 impl Output for IntegrationIO {
     fn discard(&mut self, card: CardId, player: usize) {
-        assert_eq!(self.events.remove(0), Event::Discard { card, player })
+        self.events.push(Event::Discard { card, player });
     }
     fn draw(&mut self, card: CardId, player: usize) {
-        assert_eq!(self.events.remove(0), Event::Draw { card, player })
+        self.events.push(Event::Draw { card, player });
     }
     fn clock(&mut self, card: CardId, player: usize) {
-        assert_eq!(self.events.remove(0), Event::Clock { card, player })
+        self.events.push(Event::Clock { card, player });
     }
     fn phase_change(&mut self, phase: Phase, player: usize) {
-        assert_eq!(self.events.remove(0), Event::PhaseChange { phase, player })
+        self.events.push(Event::PhaseChange { phase, player });
     }
     fn level_up(&mut self, result: LevelUpResult, player: usize) {
-        assert_eq!(self.events.remove(0), Event::LevelUp { result, player })
+        self.events.push(Event::LevelUp { result, player });
+    }
+    fn shuffle(&mut self, player: usize) {
+        self.events.push(Event::Shuffle { player });
+    }
+    fn first_player(&mut self, player: usize) {
+        self.events.push(Event::FirstPlayer { player });
+    }
+    fn mulligan(&mut self, cards: Vec<CardId>, player: usize) {
+        self.events.push(Event::Mulligan { cards, player });
     }
     fn refreshed(&mut self, player: usize) {
-        assert_eq!(self.events.remove(0), Event::Refreshed { player })
+        self.events.push(Event::Refreshed { player });
+    }
+    fn refresh_damage(&mut self, card: CardId, player: usize) {
+        self.events.push(Event::RefreshDamage { card, player });
+    }
+    fn resolve_ability(&mut self, ability: TriggeredAbility, player: usize) {
+        self.events.push(Event::ResolveAbility { ability, player });
+    }
+    fn move_character(&mut self, from: StagePosition, to: StagePosition, player: usize) {
+        self.events.push(Event::MoveCharacter { from, to, player });
+    }
+    fn play_event(&mut self, card: CardId, player: usize) {
+        self.events.push(Event::PlayEventCard { card, player });
+    }
+    fn event_resolved(&mut self, card: CardId, player: usize) {
+        self.events.push(Event::ResolvedEventCard { card, player });
+    }
+    fn play_character(&mut self, card: CardId, position: StagePosition, player: usize) {
+        self.events.push(Event::PlayCharacter {
+            card,
+            position,
+            player,
+        });
+    }
+    fn play_climax(&mut self, card: CardId, player: usize) {
+        self.events.push(Event::PlayClimax { card, player });
+    }
+    fn declare_attack(&mut self, attacker: StagePosition, attack_type: AttackType, player: usize) {
+        self.events.push(Event::DeclareAttack {
+            attacker,
+            attack_type,
+            player,
+        });
+    }
+    fn trigger(&mut self, card: CardId, player: usize) {
+        self.events.push(Event::Trigger { card, player });
+    }
+    fn trigger_effect(&mut self, icon: TriggerIcon, card: Option<CardId>, player: usize) {
+        self.events
+            .push(Event::TriggerEffect { icon, card, player });
+    }
+    fn damage_revealed(&mut self, card: CardId, player: usize) {
+        self.events.push(Event::DamageRevealed { card, player });
+    }
+    fn damage(&mut self, result: DamageResult, player: usize) {
+        self.events.push(Event::Damage { result, player });
+    }
+    fn stand(&mut self, position: StagePosition, player: usize) {
+        self.events.push(Event::Stand { position, player });
+    }
+    fn counter(&mut self, card: CardId, position: StagePosition, player: usize) {
+        self.events.push(Event::Counter {
+            card,
+            position,
+            player,
+        });
+    }
+    fn reverse(&mut self, position: StagePosition, player: usize) {
+        self.events.push(Event::Reverse { position, player });
+    }
+    fn pay_cost(&mut self, cost: Cost, cards: Vec<CardId>, player: usize) {
+        self.events.push(Event::PayCost {
+            cost,
+            cards,
+            player,
+        });
+    }
+    fn encore(
+        &mut self,
+        card: CardId,
+        position: StagePosition,
+        cost: Option<Vec<Cost>>,
+        player: usize,
+    ) {
+        self.events.push(Event::Encore {
+            card,
+            position,
+            cost,
+            player,
+        });
     }
 }
 
-impl Input<CardId> for IntegrationIO {
+impl<T> Input<T> for IntegrationIO {
     fn ask_optional_choice(
         &mut self,
-        options: &[CardId],
+        options: &[T],
         player: usize,
         context: ChoiceContext,
     ) -> Option<usize> {
-        let answer = self.may_answers.remove(0);
-        assert_eq!(answer.context, context);
-        assert_eq!(answer.player, player);
-        assert_eq!(answer.options, options);
-
-        answer.value
+        let answer = ().ask_optional_choice(options, player, context);
+        self.choices.push(Choice {
+            context,
+            player,
+            options: options.len(),
+            answer,
+        });
+        answer
     }
     fn ask_required_choice(
         &mut self,
-        options: &[CardId],
+        options: &[T],
         player: usize,
         context: ChoiceContext,
     ) -> usize {
-        let answer = self.must_answers.remove(0);
-        assert_eq!(answer.context, context);
-        assert_eq!(answer.player, player);
-        assert_eq!(answer.options, options);
+        let answer = ().ask_required_choice(options, player, context);
+        self.choices.push(Choice {
+            context,
+            player,
+            options: options.len(),
+            answer: Some(answer),
+        });
+        answer
+    }
+}
+
+impl RulesEngineIO for IntegrationIO {}
+
+/// Runs a game between two copies of the sample deck, where both
+/// players always pick the first option they're given.
+fn run_sample_game(seed: u64) -> (GameResult, IntegrationIO) {
+    let data = GameData::parse(SAMPLE_CARDS).unwrap();
+    let deck = DeckList::parse(SAMPLE_DECK, &data).unwrap();
+    assert!(deck.validate(&data).is_empty());
+
+    let mut rules = Rules::with_decks(data, [&deck, &deck], seed);
+    let mut io = IntegrationIO::default();
+    let result = rules.run_game(&mut io, Some(0));
+    (result, io)
+}
 
-        answer.value
+#[test]
+fn sample_game_ends() {
+    let (result, io) = run_sample_game(7);
+
+    assert_eq!(
+        result,
+        GameResult::Win {
+            winner: 1,
+            reason: LossReason::LevelFour
+        }
+    );
+    assert_eq!(
+        io.events[..3],
+        [
+            Event::Shuffle { player: 0 },
+            Event::Shuffle { player: 1 },
+            Event::FirstPlayer { player: 0 },
+        ]
+    );
+    assert_eq!(
+        io.choices[0],
+        Choice {
+            context: ChoiceContext::GameStartTurnOrder,
+            player: 0,
+            options: 2,
+            answer: Some(0),
+        }
+    );
+    for choice in io.choices.iter() {
+        assert!(choice.answer.is_none_or(|answer| answer < choice.options));
+    }
+
+    // Both players mulligan their whole opening hand, one card at a time.
+    let opening_hand: Vec<_> = io.events[3..8]
+        .iter()
+        .map(|event| match event {
+            Event::Draw { card, player: 0 } => *card,
+            event => panic!("expected a draw for the first player, got {:?}", event),
+        })
+        .collect();
+    assert!(io.events.contains(&Event::Mulligan {
+        cards: opening_hand,
+        player: 0
+    }));
+    for player in 0..2 {
+        let mulligans = io.choices[1..13]
+            .iter()
+            .filter(|choice| {
+                choice.context == ChoiceContext::MulliganDiscard && choice.player == player
+            })
+            .count();
+        assert_eq!(mulligans, 6);
     }
+
+    assert_eq!(io.level_ups(0), 4);
+    assert!(io.level_ups(1) < 4);
+    assert!(matches!(
+        io.events.last(),
+        Some(Event::LevelUp { player: 0, .. })
+    ));
+
+    let (replay_result, replay) = run_sample_game(7);
+    assert_eq!(replay_result, result);
+    assert_eq!(replay.events, io.events);
+    assert_eq!(replay.choices, io.choices);
 }